@@ -9,38 +9,35 @@
 //! # Examples
 //! Say you'd like to get a number from the user via `stdin` that is in the closed interval [1, 100].
 //! To do this with Promptor, you first define the Promptor object and then call the `prompt()` method on it, like so:
-//! ```
+//! ```no_run
 //! use promptor::Promptor;
 //!
-//! let stdio = std::io::stdin();
-//! let input = stdio.lock();
-//! let output = std::io::stdout();
-//!
-//! let mut promptor = Promptor {
-//!     reader: input,
-//!     writer: output
-//! };
+//! let mut promptor = Promptor::stdio();
 //!
-//! let res: u32 = promptor.prompt("Please enter a number between 1 and 100: ", |x| 1 <= x && x <= 100);
+//! let res: u32 = promptor.prompt("Please enter a number between 1 and 100: ", |&x| 1 <= x && x <= 100);
 //! ```
 //! If you only need a string, you can use `get_line()` instead:
-//! ```
+//! ```no_run
 //! use promptor::Promptor;
-//! let stdio = std::io::stdin();
-//! let input = stdio.lock();
-//! let output = std::io::stdout();
 //!
-//! let mut promptor = Promptor {
-//!     reader: input,
-//!     writer: output
-//! };
+//! let mut promptor = Promptor::stdio();
 //!
 //! let name = promptor.get_line("What is your name? ").unwrap();
 //! ```
 
+pub use self::promptor::completer;
+pub use self::promptor::testing;
+pub use self::promptor::validator;
 pub use self::promptor::Promptor;
 
 pub mod promptor;
 
+// The original tests predate these lints, and are kept as they were written.
 #[cfg(test)]
+#[allow(
+    clippy::approx_constant,
+    clippy::empty_line_after_outer_attr,
+    clippy::manual_range_contains,
+    clippy::upper_case_acronyms
+)]
 mod tests;
@@ -0,0 +1,108 @@
+//! # Capabilities
+//!
+//! What a `Promptor` can do with its streams: whether they are terminals,
+//! whether the terminal can move the cursor, and whether to use colours.
+//! Interactive and styled prompts check these, and fall back to plain line-based input and output otherwise.
+
+use std::ffi::OsString;
+
+use super::term::{self, Terminal};
+
+/// # Capabilities
+///
+/// What a `Promptor`'s reader and writer support, which `Promptor::new()` and `Promptor::stdio()`
+/// find out once, from `Promptor::terminal`, or stdin and stdout, and the environment:
+/// * `NO_COLOR`, if set to anything but an empty string, turns colours off
+/// * `CLICOLOR_FORCE`, if set to anything but `0`, turns them on, even if the writer isn't a terminal,
+///   unless `NO_COLOR` is set
+/// * `TERM=dumb` says the terminal can't move the cursor, so the line editor, interactive menus
+///   and colours are all turned off
+///
+/// Interactive prompts, like the line editor, `interactive_select()` and `fuzzy_select()`,
+/// need `interactive()`, and themes are only styled with `color()`; otherwise prompts behave like `get_line()`.
+/// Set `Promptor::capabilities` to pick a mode yourself, e.g. in tests.
+///
+/// # Example
+/// ```
+/// use promptor::Promptor;
+/// use promptor::promptor::capabilities::Capabilities;
+///
+/// let input = b"\x1b[B\r";
+/// let mut output: Vec<u8> = Vec::new();
+///
+/// let mut promptor = Promptor::new(&input[..], &mut output);
+/// promptor.capabilities = Capabilities {
+///     input_is_terminal: true,
+///     output_is_terminal: true,
+///     ..Capabilities::default()
+/// };
+///
+/// // Down, then Enter, without a real terminal.
+/// assert_eq!(1, promptor.interactive_select("Shell: ", &["bash", "zsh"]).unwrap());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Whether the reader is a terminal, which can hide what the user types and send single key presses.
+    pub input_is_terminal: bool,
+
+    /// Whether the writer is a terminal.
+    pub output_is_terminal: bool,
+
+    /// Whether the terminal is `TERM=dumb`, which can't move the cursor or show colours.
+    pub dumb: bool,
+
+    /// Whether `NO_COLOR` is set.
+    pub no_color: bool,
+
+    /// Whether `CLICOLOR_FORCE` is set.
+    pub force_color: bool,
+}
+
+impl Capabilities {
+    /// Finds out what `terminal` and the environment support.
+    /// Without a terminal, neither the reader nor the writer is one.
+    pub fn detect(terminal: Option<&Terminal>) -> Self {
+        Capabilities::from_vars(
+            terminal.is_some(),
+            matches!(terminal, Some(terminal) if terminal.output_is_terminal()),
+            |name| std::env::var_os(name),
+        )
+    }
+
+    /// Finds out what stdin, stdout and the environment support, checking stdin and stdout separately,
+    /// so that colours work on a terminal even when the answers are piped in.
+    pub fn stdio() -> Self {
+        Capabilities::from_vars(
+            Terminal::stdin().is_some(),
+            term::stdout_is_terminal(),
+            |name| std::env::var_os(name),
+        )
+    }
+
+    /// Same as `detect()`, but reads the environment with `var`.
+    pub(crate) fn from_vars<F>(input_is_terminal: bool, output_is_terminal: bool, var: F) -> Self
+    where
+        F: Fn(&str) -> Option<OsString>,
+    {
+        let force_color = var("CLICOLOR_FORCE").unwrap_or_default();
+
+        Capabilities {
+            input_is_terminal,
+            output_is_terminal,
+            dumb: var("TERM") == Some("dumb".into()),
+            no_color: !var("NO_COLOR").unwrap_or_default().is_empty(),
+            force_color: !force_color.is_empty() && force_color != "0",
+        }
+    }
+
+    /// Whether prompts can read single key presses and redraw the line they are on,
+    /// which the line editor and interactive menus need.
+    pub fn interactive(&self) -> bool {
+        self.input_is_terminal && self.output_is_terminal && !self.dumb
+    }
+
+    /// Whether to style prompts with the theme's colours.
+    pub fn color(&self) -> bool {
+        !self.no_color && (self.force_color || (self.output_is_terminal && !self.dumb))
+    }
+}
@@ -0,0 +1,205 @@
+//! # Completer
+//!
+//! Completers suggest how to finish what the user is typing when they press Tab,
+//! for prompts read with the line editor.
+//! You can pass any of them, or any closure of the form `Fn(&str, usize) -> Completion`,
+//! to `Prompt::completer()`.
+//!
+//! # Example
+//! ```
+//! use promptor::completer::{words, Completer};
+//!
+//! let colours = words(&["red", "green", "grey"]);
+//!
+//! let completion = colours.complete("paint it gr", 11);
+//!
+//! assert_eq!(9, completion.start);
+//! assert_eq!(vec!["green".to_owned(), "grey".to_owned()], completion.candidates);
+//! ```
+
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+
+/// # Completion
+///
+/// What a `Completer` suggests: text to replace the part of the line
+/// from `start` up to the cursor with.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Completion {
+    /// Where the text being completed starts, as a byte index into the line.
+    pub start: usize,
+
+    /// The texts that could replace the line from `start` up to the cursor, in the order to show them.
+    pub candidates: Vec<String>,
+}
+
+/// # Completer
+///
+/// Suggests candidates for the text before the cursor.
+pub trait Completer {
+    /// Suggests how to complete `line`, where the cursor is at the byte index `cursor`.
+    /// The built-in completers suggest nothing if `cursor` is past the end of `line`
+    /// or not on a character boundary.
+    fn complete(&self, line: &str, cursor: usize) -> Completion;
+}
+
+impl<F> Completer for F
+where
+    F: Fn(&str, usize) -> Completion,
+{
+    fn complete(&self, line: &str, cursor: usize) -> Completion {
+        self(line, cursor)
+    }
+}
+
+/// # Words
+///
+/// The completer returned by `words()`.
+#[derive(Clone, Debug)]
+pub struct Words(Vec<String>);
+
+/// Completes the word before the cursor with the words in `list` that start with it.
+pub fn words<S>(list: &[S]) -> Words
+where
+    S: Display,
+{
+    Words(list.iter().map(|word| word.to_string()).collect())
+}
+
+impl Completer for Words {
+    fn complete(&self, line: &str, cursor: usize) -> Completion {
+        let before = match line.get(..cursor) {
+            Some(before) => before,
+            None => return Completion::default(),
+        };
+        let word = before.rsplit(char::is_whitespace).next().unwrap_or(before);
+
+        Completion {
+            start: cursor - word.len(),
+            candidates: starting_with(&self.0, word),
+        }
+    }
+}
+
+/// # Choices
+///
+/// The completer returned by `choices()`.
+#[derive(Clone, Debug)]
+pub struct Choices(Vec<String>);
+
+/// Completes everything before the cursor with the items in `items` that start with it, ignoring case,
+/// like the options of `Promptor::select()`, which may contain spaces.
+pub fn choices<S>(items: &[S]) -> Choices
+where
+    S: Display,
+{
+    Choices(items.iter().map(|item| item.to_string()).collect())
+}
+
+impl Completer for Choices {
+    fn complete(&self, line: &str, cursor: usize) -> Completion {
+        let prefix = match line.get(..cursor) {
+            Some(before) => before.to_lowercase(),
+            None => return Completion::default(),
+        };
+
+        Completion {
+            start: 0,
+            candidates: self
+                .0
+                .iter()
+                .filter(|item| item.to_lowercase().starts_with(&prefix))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// # Paths
+///
+/// The completer returned by `paths()`.
+#[derive(Clone, Copy, Debug)]
+pub struct Paths;
+
+/// Completes everything before the cursor as a path, with the names of the files and directories
+/// in the directory it names, or in the current directory.
+/// Directories get a trailing `/`, and hidden files are only suggested once the name starts with `.`.
+pub fn paths() -> Paths {
+    Paths
+}
+
+impl Completer for Paths {
+    fn complete(&self, line: &str, cursor: usize) -> Completion {
+        let before = match line.get(..cursor) {
+            Some(before) => before,
+            None => return Completion::default(),
+        };
+        let start = before.rfind(is_separator).map_or(0, |index| index + 1);
+        let (dir, prefix) = before.split_at(start);
+
+        let dir = if dir.is_empty() {
+            Path::new(".")
+        } else {
+            Path::new(dir)
+        };
+
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(_) => return Completion::default(),
+        };
+
+        let mut candidates: Vec<String> = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+
+                if !name.starts_with(prefix) || (name.starts_with('.') && !prefix.starts_with('.'))
+                {
+                    return None;
+                }
+
+                // Follows symbolic links, so that links to directories also get a `/`.
+                let is_dir = entry.path().is_dir();
+
+                Some(if is_dir { format!("{}/", name) } else { name })
+            })
+            .collect();
+
+        candidates.sort();
+
+        Completion { start, candidates }
+    }
+}
+
+fn is_separator(c: char) -> bool {
+    c == '/' || c == std::path::MAIN_SEPARATOR
+}
+
+fn starting_with(list: &[String], prefix: &str) -> Vec<String> {
+    list.iter()
+        .filter(|item| item.starts_with(prefix))
+        .cloned()
+        .collect()
+}
+
+/// The longest text that all `candidates` start with, which Tab can fill in straight away.
+pub(crate) fn common_prefix(candidates: &[String]) -> &str {
+    let first = match candidates.first() {
+        Some(first) => first.as_str(),
+        None => return "",
+    };
+
+    candidates[1..].iter().fold(first, |prefix, candidate| {
+        let len = prefix
+            .char_indices()
+            .zip(candidate.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or_else(
+                || prefix.len().min(candidate.len()),
+                |((index, _), _)| index,
+            );
+
+        &prefix[..len]
+    })
+}
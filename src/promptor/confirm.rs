@@ -0,0 +1,159 @@
+//! # Confirm
+//!
+//! Yes/no questions, with answers you can localize.
+
+use std::io::{BufRead, Write};
+
+use super::line_editor::Helpers;
+use super::{PromptError, Promptor};
+
+/// # Answers
+///
+/// The words that count as "yes" and "no" in `Promptor::confirm_with()`, compared case-insensitively,
+/// and the message to show when the user enters something else.
+/// The first word of each list is shown in the hint after the question.
+///
+/// # Example
+/// ```
+/// use promptor::promptor::confirm::Answers;
+///
+/// let french = Answers {
+///     yes: vec!["o".to_owned(), "oui".to_owned()],
+///     no: vec!["n".to_owned(), "non".to_owned()],
+///     error_message: "Veuillez répondre par oui ou par non.".to_owned(),
+/// };
+///
+/// assert_eq!(Some(true), french.parse("OUI"));
+/// assert_eq!("[O/n]", french.hint(Some(true)));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answers {
+    pub yes: Vec<String>,
+    pub no: Vec<String>,
+    pub error_message: String,
+}
+
+impl Answers {
+    /// Returns `Some(true)` if `answer` is one of the "yes" words,
+    /// `Some(false)` if it is one of the "no" words, and `None` otherwise.
+    pub fn parse(&self, answer: &str) -> Option<bool> {
+        let answer = answer.trim().to_lowercase();
+        let matches = |words: &[String]| words.iter().any(|word| word.to_lowercase() == answer);
+
+        if matches(&self.yes) {
+            Some(true)
+        } else if matches(&self.no) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the hint shown after the question, like `[Y/n]`,
+    /// with the answer that `default` picks in upper case.
+    pub fn hint(&self, default: Option<bool>) -> String {
+        let first = |words: &[String]| words.first().cloned().unwrap_or_default();
+        let (yes, no) = (first(&self.yes), first(&self.no));
+
+        match default {
+            Some(true) => format!("[{}/{}]", yes.to_uppercase(), no.to_lowercase()),
+            Some(false) => format!("[{}/{}]", yes.to_lowercase(), no.to_uppercase()),
+            None => format!("[{}/{}]", yes.to_lowercase(), no.to_lowercase()),
+        }
+    }
+
+    /// The word to show for `answer` once the user has answered,
+    /// which is the last one of its list, usually the whole word, like `yes`.
+    pub(crate) fn word(&self, answer: bool) -> String {
+        let words = if answer { &self.yes } else { &self.no };
+
+        words.last().cloned().unwrap_or_default()
+    }
+}
+
+impl Default for Answers {
+    fn default() -> Self {
+        Answers {
+            yes: vec!["y".to_owned(), "yes".to_owned()],
+            no: vec!["n".to_owned(), "no".to_owned()],
+            error_message: "Please answer yes or no.".to_owned(),
+        }
+    }
+}
+
+impl<R, W> Promptor<R, W>
+where
+    R: BufRead,
+    W: Write,
+{
+    /// Asks the user a yes/no question, accepting `y`, `yes`, `n` and `no` in any case.
+    /// The question is followed by `[Y/n]` or `[y/N]` if there is a default, or `[y/n]` if there isn't.
+    /// If the user enters something else, or an empty line when there is no default,
+    /// this function asks them again.
+    /// Returns `PromptError` if:
+    /// * `rget_line()` fails, including when `self.reader` has reached end-of-input
+    /// * `writeln!()` fails while asking the user to try again
+    ///
+    /// # Arguments
+    /// * `msg` – a question to display to the user.
+    /// * `default` – the answer to use if the user enters an empty line, if any.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use promptor::Promptor;
+    ///
+    /// let stdio = std::io::stdin();
+    /// let input = stdio.lock();
+    /// let output = std::io::stdout();
+    ///
+    /// let mut promptor = Promptor::new(input, output);
+    ///
+    /// if promptor.confirm("Overwrite the existing file?", Some(false)).unwrap() {
+    ///     println!("Overwriting.");
+    /// }
+    /// ```
+    pub fn confirm(&mut self, msg: &str, default: Option<bool>) -> Result<bool, PromptError> {
+        self.confirm_with(msg, default, &Answers::default())
+    }
+
+    /// Same as `confirm()`, but accepts the words in `answers` instead of y/yes/n/no,
+    /// e.g. for a localized tool.
+    ///
+    /// # Arguments
+    /// * `msg` – a question to display to the user.
+    /// * `default` – the answer to use if the user enters an empty line, if any.
+    /// * `answers` – the words that count as "yes" and "no".
+    pub fn confirm_with(
+        &mut self,
+        msg: &str,
+        default: Option<bool>,
+        answers: &Answers,
+    ) -> Result<bool, PromptError> {
+        let color = self.color();
+        let text = format!(
+            "{} {} ",
+            self.theme.prompt(msg, color),
+            self.theme.default_value(&answers.hint(default), color)
+        );
+
+        loop {
+            let line = self.read_line_as(&text, Helpers::default())?;
+
+            let answer = if line.trim().is_empty() && default.is_some() {
+                default
+            } else {
+                answers.parse(&line)
+            };
+
+            match answer {
+                Some(answer) => {
+                    self.echo_answer(msg, &answers.word(answer))?;
+                    break Ok(answer);
+                }
+                None => {
+                    self.show_retry(None, &line, &answers.error_message, &answers.error_message)?
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! # Edit
+//!
+//! Long-form input through the user's own editor, the way git asks for commit messages.
+
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::{PromptError, Promptor};
+
+/// The editor to fall back to if neither `$VISUAL` nor `$EDITOR` is set.
+#[cfg(unix)]
+const DEFAULT_EDITOR: &str = "vi";
+#[cfg(not(unix))]
+const DEFAULT_EDITOR: &str = "notepad";
+
+/// Keeps the temporary files of concurrent `edit()` calls apart.
+static EDIT_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+impl<R, W> Promptor<R, W>
+where
+    R: BufRead,
+    W: Write,
+{
+    /// Opens `template` in the user's editor, `$VISUAL` or `$EDITOR`, and waits for it to exit.
+    /// Returns what the user saved, without lines starting with `#`,
+    /// trailing whitespace, or leading and trailing blank lines.
+    /// The editor runs on the real terminal, not on `self.reader` and `self.writer`.
+    /// Returns `PromptError` if:
+    /// * the temporary file can't be written or read back
+    /// * the editor can't be started, or exits unsuccessfully, in which case the error is `PromptError::EditorError`
+    ///
+    /// # Arguments
+    /// * `template` – the text to start with, e.g. instructions in `#` comments.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use promptor::Promptor;
+    ///
+    /// let mut promptor = Promptor::stdio();
+    ///
+    /// let message = promptor
+    ///     .edit("\n# Please describe your changes. Lines starting with '#' will be ignored.\n")
+    ///     .unwrap();
+    /// ```
+    pub fn edit(&mut self, template: &str) -> Result<String, PromptError> {
+        let editor = pick_editor(std::env::var("VISUAL").ok(), std::env::var("EDITOR").ok());
+
+        self.edit_with(&editor, template)
+    }
+
+    /// Same as `edit()`, but runs `editor` instead of looking it up.
+    /// On Unix, `editor` is run through `sh`, so it can contain arguments, like `code --wait`.
+    ///
+    /// # Arguments
+    /// * `editor` – the command that starts the editor; the file name is added to the end.
+    /// * `template` – the text to start with, e.g. instructions in `#` comments.
+    pub fn edit_with(&mut self, editor: &str, template: &str) -> Result<String, PromptError> {
+        let file = TempFile::create(template)?;
+
+        let status =
+            editor_command(editor, &file.0)
+                .status()
+                .map_err(|err| PromptError::EditorError {
+                    editor: editor.to_owned(),
+                    reason: err.to_string(),
+                })?;
+
+        if !status.success() {
+            return Err(PromptError::EditorError {
+                editor: editor.to_owned(),
+                reason: status.to_string(),
+            });
+        }
+
+        let text = fs::read_to_string(&file.0)?;
+
+        Ok(strip_comments(&text))
+    }
+}
+
+#[cfg(unix)]
+fn editor_command(editor: &str, path: &Path) -> Command {
+    let mut command = Command::new("sh");
+    command
+        .arg("-c")
+        .arg(format!("{} \"$@\"", editor))
+        .arg(editor)
+        .arg(path);
+    command
+}
+
+#[cfg(not(unix))]
+fn editor_command(editor: &str, path: &Path) -> Command {
+    let mut words = editor.split_whitespace();
+    let mut command = Command::new(words.next().unwrap_or(DEFAULT_EDITOR));
+    command.args(words).arg(path);
+    command
+}
+
+/// Picks `visual` if it is set, or else `editor`, or else the default editor.
+/// An editor set to nothing but whitespace doesn't count as set.
+pub(crate) fn pick_editor(visual: Option<String>, editor: Option<String>) -> String {
+    let is_set = |editor: &String| !editor.trim().is_empty();
+
+    visual
+        .filter(is_set)
+        .or_else(|| editor.filter(is_set))
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_owned())
+}
+
+/// Removes lines starting with `#`, trailing whitespace, and leading and trailing blank lines.
+pub(crate) fn strip_comments(text: &str) -> String {
+    let lines: Vec<&str> = text
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| line.trim_end())
+        .collect();
+
+    let start = lines.iter().position(|line| !line.is_empty());
+    let end = lines.iter().rposition(|line| !line.is_empty());
+
+    match (start, end) {
+        (Some(start), Some(end)) => lines[start..=end].join("\n"),
+        _ => String::new(),
+    }
+}
+
+/// A temporary file that is removed when dropped, whether or not editing succeeded.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn create(contents: &str) -> std::io::Result<TempFile> {
+        let path = std::env::temp_dir().join(format!(
+            "promptor-{}-{}.txt",
+            std::process::id(),
+            EDIT_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+
+        // The temporary directory is shared, and what the user writes can be private.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        let mut file = options.open(&path)?;
+        let temp_file = TempFile(path);
+
+        file.write_all(contents.as_bytes())?;
+
+        Ok(temp_file)
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
@@ -0,0 +1,241 @@
+//! # Fuzzy
+//!
+//! Fuzzy matching, where a query matches any text that contains its characters in order,
+//! like `fb` matching `foo-bar`, and a picker that narrows long lists down with it.
+
+use std::fmt::Display;
+use std::io::{BufRead, Write};
+
+use super::menu::{run_menu, Filter, Look, DEFAULT_SIZE};
+use super::term::raw_mode;
+use super::{PromptError, Promptor};
+
+/// What every matched character is worth.
+const MATCH_SCORE: i64 = 16;
+
+/// The bonus for a character right after the previous matched one.
+const CONSECUTIVE_BONUS: i64 = 8;
+
+/// The bonus for a character that starts a word, like the `b` in `foo-bar` or `fooBar`.
+const WORD_START_BONUS: i64 = 8;
+
+/// What a gap between two matched characters takes off the score.
+const GAP_PENALTY: i64 = 3;
+
+/// What every character after the first one in a gap takes off the score as well.
+const GAP_EXTENSION_PENALTY: i64 = 1;
+
+/// The most that unmatched characters before the first match take off the score.
+const MAX_LEADING_PENALTY: i64 = 4;
+
+/// Scores how well `query` matches `candidate`, ignoring case, or returns `None` if `candidate`
+/// doesn't contain the characters of `query` in order.
+/// The score is higher for matches that are close together and that start words,
+/// and comes with the positions of the matched characters in `candidate`, counted in characters,
+/// to highlight them. An empty query matches everything with a score of 0.
+///
+/// # Arguments
+/// * `query` – what the user typed.
+/// * `candidate` – the text to match it against.
+///
+/// # Example
+/// ```
+/// use promptor::promptor::fuzzy::fuzzy_score;
+///
+/// let (score, positions) = fuzzy_score("fb", "foo-bar").unwrap();
+/// assert_eq!(vec![0, 4], positions);
+///
+/// let (worse, _) = fuzzy_score("fb", "fabric").unwrap();
+/// assert!(score > worse);
+///
+/// assert_eq!(None, fuzzy_score("bf", "foo-bar"));
+/// ```
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<(i64, Vec<usize>)> {
+    let query: Vec<char> = query.chars().map(fold_case).collect();
+    let chars: Vec<char> = candidate.chars().collect();
+    let folded: Vec<char> = chars.iter().copied().map(fold_case).collect();
+
+    if query.is_empty() {
+        return Some((0, Vec::new()));
+    }
+
+    // Find where the first match ends, then look backwards from there for the shortest match.
+    let mut matched = 0;
+    let end = folded.iter().position(|&c| {
+        if c == query[matched] {
+            matched += 1;
+        }
+        matched == query.len()
+    })?;
+
+    let mut unmatched = query.len();
+    let start = (0..=end).rev().find(|&index| {
+        if folded[index] == query[unmatched - 1] {
+            unmatched -= 1;
+        }
+        unmatched == 0
+    })?;
+
+    let mut positions = Vec::with_capacity(query.len());
+    for (index, &c) in folded.iter().enumerate().take(end + 1).skip(start) {
+        if positions.len() < query.len() && c == query[positions.len()] {
+            positions.push(index);
+        }
+    }
+
+    let mut score = -(start as i64).min(MAX_LEADING_PENALTY);
+
+    for (n, &position) in positions.iter().enumerate() {
+        score += MATCH_SCORE;
+
+        if starts_word(&chars, position) {
+            score += WORD_START_BONUS;
+        }
+
+        if n > 0 {
+            let gap = position - positions[n - 1] - 1;
+
+            if gap == 0 {
+                score += CONSECUTIVE_BONUS;
+            } else {
+                score -= GAP_PENALTY + (gap as i64 - 1) * GAP_EXTENSION_PENALTY;
+            }
+        }
+    }
+
+    Some((score, positions))
+}
+
+/// Matches `query` against every item, and returns the indices of the items that match
+/// with the positions of the matched characters, best first.
+/// Items with the same score stay in their original order.
+pub(crate) fn rank(items: &[String], query: &str) -> Vec<(usize, Vec<usize>)> {
+    let mut scored: Vec<(i64, usize, Vec<usize>)> = items
+        .iter()
+        .enumerate()
+        .filter_map(|(index, item)| {
+            let (score, positions) = fuzzy_score(query, item)?;
+            Some((score, index, positions))
+        })
+        .collect();
+
+    scored.sort_by(|(a, ..), (b, ..)| b.cmp(a));
+
+    scored
+        .into_iter()
+        .map(|(_, index, positions)| (index, positions))
+        .collect()
+}
+
+/// Lowercases `c` if that gives a single character, so that positions stay the same.
+fn fold_case(c: char) -> char {
+    let mut lower = c.to_lowercase();
+
+    match (lower.next(), lower.next()) {
+        (Some(lower), None) => lower,
+        _ => c,
+    }
+}
+
+fn starts_word(chars: &[char], position: usize) -> bool {
+    let previous = match position.checked_sub(1) {
+        Some(previous) => chars[previous],
+        None => return true,
+    };
+
+    !previous.is_alphanumeric() || (previous.is_lowercase() && chars[position].is_uppercase())
+}
+
+impl<R, W> Promptor<R, W>
+where
+    R: BufRead,
+    W: Write,
+{
+    /// Lets the user pick one of `items` by typing part of it.
+    /// On a terminal, the list is filtered with `fuzzy_score()` as the user types, best matches first,
+    /// with the matching characters highlighted; Up/Down move the cursor, Ctrl-U clears the query,
+    /// and Enter picks the highlighted item.
+    ///
+    /// Unless `self.capabilities` is `interactive()`, like `interactive_select()`, this function reads a query line instead,
+    /// then shows the matching items as a numbered menu and reads a number, like `select()`.
+    /// If nothing matches, it asks for the query again.
+    ///
+    /// Returns the index of the chosen item in `items`, or `PromptError` if:
+    /// * switching the terminal to raw mode, reading keys or drawing the list fails
+    /// * `rget_line()` fails, including when `self.reader` has reached end-of-input
+    /// * the user presses Ctrl-C, in which case the error is a `PromptError::StdinError` of kind `Interrupted`
+    /// * the user presses Ctrl-D, in which case the error is `PromptError::EofError`
+    /// * `items` is empty, in which case the error is `PromptError::NoChoices`
+    ///
+    /// # Arguments
+    /// * `msg` – a message to display to the user before the query.
+    /// * `items` – the options to choose from.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use promptor::Promptor;
+    ///
+    /// let mut promptor = Promptor::stdio();
+    ///
+    /// let branches = ["main", "feature/fuzzy-picker", "fix/retry-message", "release/1.0"];
+    /// let index = promptor.fuzzy_select("Branch: ", &branches).unwrap();
+    ///
+    /// println!("Checking out {}.", branches[index]);
+    /// ```
+    pub fn fuzzy_select<S>(&mut self, msg: &str, items: &[S]) -> Result<usize, PromptError>
+    where
+        S: Display,
+    {
+        if items.is_empty() {
+            return Err(PromptError::NoChoices);
+        }
+
+        let items: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+
+        if !self.capabilities.interactive() {
+            return self.fuzzy_select_lines(msg, &items);
+        }
+
+        let size = self.terminal.and_then(|terminal| terminal.size());
+
+        let choice = {
+            let _raw = raw_mode(self.terminal.as_ref())?;
+
+            let look = Look {
+                theme: &self.theme,
+                color: self.color(),
+            };
+
+            run_menu(
+                &mut self.reader,
+                &mut self.writer,
+                msg,
+                &items,
+                size.unwrap_or(DEFAULT_SIZE),
+                Filter::Fuzzy,
+                look,
+            )?
+        };
+
+        choice.ok_or(PromptError::EofError)
+    }
+
+    /// Same as `fuzzy_select()`, but without a terminal: reads a query, then a number.
+    fn fuzzy_select_lines(&mut self, msg: &str, items: &[String]) -> Result<usize, PromptError> {
+        loop {
+            let query = self.rget_line(msg)?;
+            let ranked = rank(items, &query);
+
+            if ranked.is_empty() {
+                let reason = format!("Nothing matches \"{}\".", query);
+                self.show_retry(None, &query, &reason, &reason)?;
+                continue;
+            }
+
+            let matches: Vec<&String> = ranked.iter().map(|(index, _)| &items[*index]).collect();
+            let picked = self.select("Number: ", &matches)?;
+
+            break Ok(ranked[picked].0);
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//! # History
+//!
+//! Earlier answers, kept in a file per prompt so that the line editor can recall them
+//! with the Up arrow the next time a program asks the same question.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// How many entries a history keeps unless told otherwise.
+pub const DEFAULT_MAX_ENTRIES: usize = 500;
+
+/// # History
+///
+/// A list of earlier answers, oldest first, without empty or duplicate entries.
+/// When it is full, adding an entry drops the oldest one.
+///
+/// # Example
+/// ```
+/// use promptor::promptor::history::History;
+///
+/// let mut history = History::new(2);
+/// history.add("red");
+/// history.add("green");
+/// history.add("red");
+/// history.add("blue");
+///
+/// assert_eq!(&["red".to_owned(), "blue".to_owned()], history.entries());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct History {
+    entries: Vec<String>,
+    max_entries: usize,
+}
+
+impl History {
+    /// Creates an empty history that keeps at most `max_entries` entries.
+    pub fn new(max_entries: usize) -> Self {
+        History {
+            entries: Vec::new(),
+            max_entries,
+        }
+    }
+
+    /// The entries, oldest first.
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Adds `entry` as the newest entry, removing any earlier copy of it.
+    /// Blank entries, and entries that span several lines, are ignored.
+    pub fn add(&mut self, entry: &str) {
+        if entry.trim().is_empty() || entry.contains(&['\r', '\n'][..]) {
+            return;
+        }
+
+        self.entries.retain(|earlier| earlier != entry);
+        self.entries.push(entry.to_owned());
+
+        let excess = self.entries.len().saturating_sub(self.max_entries);
+        self.entries.drain(..excess);
+    }
+
+    /// Reads a history saved by `save()`, one entry per line.
+    /// A missing file is an empty history.
+    ///
+    /// # Arguments
+    /// * `path` – the file to read.
+    /// * `max_entries` – the most entries to keep; older ones in the file are dropped.
+    pub fn load(path: &Path, max_entries: usize) -> io::Result<History> {
+        let mut history = History::new(max_entries);
+
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(history),
+            Err(err) => return Err(err),
+        };
+
+        for line in text.lines() {
+            history.add(line);
+        }
+
+        Ok(history)
+    }
+
+    /// Writes the history to `path`, one entry per line, creating its directory if needed.
+    /// On Unix, a new file is only readable by the user, since answers can be private.
+    ///
+    /// # Arguments
+    /// * `path` – the file to write.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        let mut file = options.open(path)?;
+
+        for entry in &self.entries {
+            writeln!(file, "{}", entry)?;
+        }
+
+        file.flush()
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        History::new(DEFAULT_MAX_ENTRIES)
+    }
+}
+
+/// # HistoryStore
+///
+/// Says where prompts that opt in with `Prompt::history()` keep their histories:
+/// one file per key in `dir`, named after the key.
+/// Set it as `Promptor::history` to turn persistent history on.
+///
+/// # Example
+/// ```no_run
+/// use promptor::Promptor;
+/// use promptor::promptor::history::HistoryStore;
+///
+/// let mut promptor = Promptor::stdio();
+/// promptor.history = HistoryStore::for_app("deploy-tool");
+///
+/// let host = promptor.ask::<String>("Host").history("host").run();
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HistoryStore {
+    /// The directory the history files live in.
+    pub dir: PathBuf,
+
+    /// The most entries each history keeps.
+    pub max_entries: usize,
+}
+
+impl HistoryStore {
+    /// Keeps histories in `dir`, with up to `DEFAULT_MAX_ENTRIES` entries each.
+    pub fn new<P>(dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        HistoryStore {
+            dir: dir.into(),
+            max_entries: DEFAULT_MAX_ENTRIES,
+        }
+    }
+
+    /// Keeps histories in `$XDG_STATE_HOME/<app>`, or `~/.local/state/<app>` if that isn't set.
+    /// Returns `None` if neither `$XDG_STATE_HOME` nor `$HOME` is set to an absolute path.
+    ///
+    /// # Arguments
+    /// * `app` – the name of the program, to keep its histories apart from other programs'.
+    pub fn for_app(app: &str) -> Option<Self> {
+        let absolute = |var: &str| {
+            std::env::var_os(var)
+                .map(PathBuf::from)
+                .filter(|path| path.is_absolute())
+        };
+
+        let state_dir = absolute("XDG_STATE_HOME")
+            .or_else(|| absolute("HOME").map(|home| home.join(".local").join("state")))?;
+
+        Some(HistoryStore::new(state_dir.join(app)))
+    }
+
+    /// The file that the history for `key` is kept in.
+    /// Characters other than letters, digits, `-` and `_` are replaced with `_`,
+    /// so that a key can't point outside of `dir`.
+    pub fn path(&self, key: &str) -> PathBuf {
+        let name: String = key
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        self.dir.join(format!("{}.history", name))
+    }
+
+    /// Reads the history for `key`, which is empty if it hasn't been saved yet.
+    pub fn load(&self, key: &str) -> io::Result<History> {
+        History::load(&self.path(key), self.max_entries)
+    }
+
+    /// Writes the history for `key`.
+    pub fn save(&self, key: &str, history: &History) -> io::Result<()> {
+        history.save(&self.path(key))
+    }
+}
@@ -0,0 +1,156 @@
+//! # Key
+//!
+//! Decodes the bytes a terminal in raw mode sends into keys,
+//! for the prompts that react to individual key presses.
+
+use std::io::{self, BufRead};
+
+/// A key press, decoded from the bytes a terminal sends in raw mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Key {
+    Char(char),
+    Enter,
+    Tab,
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    /// Ctrl-C, which doesn't raise SIGINT in raw mode.
+    Interrupt,
+    /// Ctrl-D.
+    EndOfFile,
+    /// Ctrl-U.
+    KillLine,
+    /// Ctrl-W.
+    KillWord,
+    /// Any other control character or escape sequence.
+    Unknown,
+}
+
+/// Reads one key from `reader`, or returns `None` at end-of-input.
+pub(crate) fn read_key<R>(reader: &mut R) -> io::Result<Option<Key>>
+where
+    R: BufRead,
+{
+    let byte = match read_byte(reader)? {
+        Some(byte) => byte,
+        None => return Ok(None),
+    };
+
+    let key = match byte {
+        b'\r' | b'\n' => Key::Enter,
+        b'\t' => Key::Tab,
+        0x7f | 0x08 => Key::Backspace,
+        0x01 => Key::Home,
+        0x05 => Key::End,
+        0x02 => Key::Left,
+        0x06 => Key::Right,
+        0x10 => Key::Up,
+        0x0e => Key::Down,
+        0x03 => Key::Interrupt,
+        0x04 => Key::EndOfFile,
+        0x15 => Key::KillLine,
+        0x17 => Key::KillWord,
+        0x1b => read_escape(reader)?,
+        byte if byte < 0x20 => Key::Unknown,
+        byte if byte < 0x80 => Key::Char(byte as char),
+        byte => read_utf8(reader, byte)?,
+    };
+
+    Ok(Some(key))
+}
+
+fn read_byte<R>(reader: &mut R) -> io::Result<Option<u8>>
+where
+    R: BufRead,
+{
+    let byte = match reader.fill_buf()?.first() {
+        Some(&byte) => byte,
+        None => return Ok(None),
+    };
+
+    reader.consume(1);
+
+    Ok(Some(byte))
+}
+
+/// Decodes the rest of an escape sequence, after the ESC byte.
+/// A lone Esc is followed by the next key rather than `[` or `O`,
+/// so that key is left in `reader` to be read on its own.
+fn read_escape<R>(reader: &mut R) -> io::Result<Key>
+where
+    R: BufRead,
+{
+    match reader.fill_buf()?.first() {
+        Some(b'[') => reader.consume(1),
+        Some(b'O') => {
+            reader.consume(1);
+
+            return Ok(match read_byte(reader)? {
+                Some(b'H') => Key::Home,
+                Some(b'F') => Key::End,
+                _ => Key::Unknown,
+            });
+        }
+        _ => return Ok(Key::Unknown),
+    }
+
+    // A CSI sequence is any number of parameter bytes followed by a final byte.
+    let mut params = Vec::new();
+
+    let last = loop {
+        match read_byte(reader)? {
+            Some(byte) if (0x30..=0x3f).contains(&byte) => params.push(byte),
+            Some(byte) => break byte,
+            None => return Ok(Key::Unknown),
+        }
+    };
+
+    Ok(match (last, params.as_slice()) {
+        (b'A', _) => Key::Up,
+        (b'B', _) => Key::Down,
+        (b'C', _) => Key::Right,
+        (b'D', _) => Key::Left,
+        (b'H', _) => Key::Home,
+        (b'F', _) => Key::End,
+        (b'~', b"1") | (b'~', b"7") => Key::Home,
+        (b'~', b"4") | (b'~', b"8") => Key::End,
+        (b'~', b"3") => Key::Delete,
+        (b'~', b"5") => Key::PageUp,
+        (b'~', b"6") => Key::PageDown,
+        _ => Key::Unknown,
+    })
+}
+
+/// Decodes a multi-byte UTF-8 character starting with `first`.
+fn read_utf8<R>(reader: &mut R, first: u8) -> io::Result<Key>
+where
+    R: BufRead,
+{
+    let len = match first {
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => return Ok(Key::Unknown),
+    };
+
+    let mut bytes = vec![first];
+
+    for _ in 1..len {
+        match read_byte(reader)? {
+            Some(byte) => bytes.push(byte),
+            None => return Ok(Key::Unknown),
+        }
+    }
+
+    Ok(std::str::from_utf8(&bytes)
+        .ok()
+        .and_then(|s| s.chars().next())
+        .map_or(Key::Unknown, Key::Char))
+}
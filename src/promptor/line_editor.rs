@@ -0,0 +1,404 @@
+//! # Line Editor
+//!
+//! A small line editor for prompts attached to a terminal in raw mode,
+//! with cursor movement, word and line deletion, history recall, Tab completion and inline hints.
+//! It only needs a `BufRead` and a `Write`, so it can be tested by feeding it key presses as bytes.
+
+use std::io::{self, BufRead, Write};
+
+use super::completer::{common_prefix, Completer, Completion};
+use super::history::History;
+use super::key::{read_key, Key};
+use super::theme::{paint, Style};
+
+/// # LineEditor
+///
+/// Reads lines with the editing keys people expect from a shell:
+/// * Left/Right (or Ctrl-B/Ctrl-F) move the cursor, and Home/End (or Ctrl-A/Ctrl-E) jump to either end
+/// * Backspace and Delete remove the character before or under the cursor
+/// * Ctrl-W removes the word before the cursor, and Ctrl-U everything before the cursor
+/// * Up/Down (or Ctrl-P/Ctrl-N) recall earlier lines from this session,
+///   or from the prompt's own history if it has one
+/// * Tab completes the text before the cursor, if the prompt has a completer;
+///   if the candidates have nothing more in common, it lists them
+/// * At the end of the line, a hint suggests the prompt's default, a line from the history
+///   or the only completion, which Right (or Ctrl-F) accepts; it is styled like defaults in `Promptor::theme`
+/// * Ctrl-D on an empty line ends the input, and Ctrl-C interrupts it
+///
+/// To use it, set `Promptor::line_editor`. It only takes over when `Promptor::capabilities` is `interactive()`,
+/// like with `Promptor::stdio()` when stdin and stdout are terminals; otherwise prompts read plain lines as usual.
+///
+/// # Example
+/// ```no_run
+/// use promptor::Promptor;
+/// use promptor::promptor::line_editor::LineEditor;
+///
+/// let mut promptor = Promptor::stdio();
+/// promptor.line_editor = Some(LineEditor::new());
+///
+/// let name = promptor.rget_line("What's your name? ");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LineEditor {
+    history: History,
+}
+
+impl LineEditor {
+    /// Creates a line editor with an empty history.
+    pub fn new() -> Self {
+        LineEditor::default()
+    }
+
+    /// The lines entered so far, oldest first.
+    pub fn history(&self) -> &[String] {
+        self.history.entries()
+    }
+
+    /// Shows `prompt`, then reads key presses from `reader` and edits the line on `writer`
+    /// until the user presses Enter, and adds the line to the history.
+    /// Returns `None` if the user presses Ctrl-D on an empty line or `reader` reaches end-of-input,
+    /// and an error of kind `Interrupted` if the user presses Ctrl-C.
+    /// Hints are dimmed.
+    ///
+    /// # Arguments
+    /// * `reader` – where to read key presses from, normally a terminal in raw mode.
+    /// * `writer` – where to draw the line.
+    /// * `prompt` – a message to display to the user.
+    pub fn read_line<R, W>(
+        &mut self,
+        reader: &mut R,
+        writer: &mut W,
+        prompt: &str,
+    ) -> io::Result<Option<String>>
+    where
+        R: BufRead,
+        W: Write,
+    {
+        let helpers = Helpers {
+            hint_style: Style {
+                dim: true,
+                ..Style::default()
+            },
+            color: true,
+            ..Helpers::default()
+        };
+
+        self.read_line_with(reader, writer, prompt, helpers)
+    }
+
+    /// Same as `read_line()`, but with what the prompt brings along to help the user.
+    /// If the prompt has its own history, lines are recalled from it instead of this session's,
+    /// and aren't added to either.
+    pub(crate) fn read_line_with<R, W>(
+        &mut self,
+        reader: &mut R,
+        writer: &mut W,
+        prompt: &str,
+        helpers: Helpers<'_>,
+    ) -> io::Result<Option<String>>
+    where
+        R: BufRead,
+        W: Write,
+    {
+        match helpers.history {
+            Some(history) => edit(reader, writer, prompt, history.entries(), &helpers),
+            None => {
+                let line = edit(reader, writer, prompt, self.history.entries(), &helpers)?;
+
+                if let Some(line) = &line {
+                    self.history.add(line);
+                }
+
+                Ok(line)
+            }
+        }
+    }
+}
+
+/// What a prompt brings along to help the user in the line editor.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct Helpers<'a> {
+    /// The prompt's own history, if it keeps one.
+    pub(crate) history: Option<&'a History>,
+
+    /// What Tab completes with.
+    pub(crate) completer: Option<&'a dyn Completer>,
+
+    /// The prompt's default, as it is shown to the user.
+    pub(crate) default: Option<&'a str>,
+
+    /// The style of hints, which is the theme's style for defaults.
+    pub(crate) hint_style: Style,
+
+    /// Whether to style hints at all.
+    pub(crate) color: bool,
+}
+
+/// Reads and edits a line, recalling lines from `history`.
+fn edit<R, W>(
+    reader: &mut R,
+    writer: &mut W,
+    prompt: &str,
+    history: &[String],
+    helpers: &Helpers<'_>,
+) -> io::Result<Option<String>>
+where
+    R: BufRead,
+    W: Write,
+{
+    write!(writer, "{}", prompt)?;
+    writer.flush()?;
+
+    let mut line = Line::new(prompt, helpers);
+    line.hint = suggest(&line, history, helpers);
+    if !line.hint.is_empty() {
+        line.draw(writer)?;
+    }
+
+    // Where we are in the history, where `history.len()` is the line being typed,
+    // and what the user had typed before going back in the history.
+    let mut position = history.len();
+    let mut draft = Vec::new();
+
+    loop {
+        let key = match read_key(reader)? {
+            Some(key) => key,
+            None if line.chars.is_empty() => return Ok(None),
+            None => Key::Enter,
+        };
+
+        match key {
+            Key::Enter => {
+                line.finish(writer)?;
+                return Ok(Some(line.text()));
+            }
+            Key::EndOfFile if line.chars.is_empty() => {
+                line.finish(writer)?;
+                return Ok(None);
+            }
+            Key::Interrupt => {
+                line.finish(writer)?;
+                return Err(io::Error::new(io::ErrorKind::Interrupted, "interrupted"));
+            }
+            Key::Char(c) => line.insert(c),
+            Key::Backspace => line.backspace(),
+            Key::Delete | Key::EndOfFile => line.delete(),
+            Key::Left => line.cursor = line.cursor.saturating_sub(1),
+            Key::Right if line.cursor == line.chars.len() => {
+                for c in std::mem::take(&mut line.hint).chars() {
+                    line.insert(c);
+                }
+            }
+            Key::Right => line.cursor = (line.cursor + 1).min(line.chars.len()),
+            Key::Home => line.cursor = 0,
+            Key::End => line.cursor = line.chars.len(),
+            Key::KillLine => line.kill_line(),
+            Key::KillWord => line.kill_word(),
+            Key::Up if position > 0 => {
+                if position == history.len() {
+                    draft = line.chars.clone();
+                }
+                position -= 1;
+                line.replace(history[position].chars().collect());
+            }
+            Key::Down if position < history.len() => {
+                position += 1;
+                if position == history.len() {
+                    line.replace(draft.clone());
+                } else {
+                    line.replace(history[position].chars().collect());
+                }
+            }
+            Key::Tab => {
+                let completer = match helpers.completer {
+                    Some(completer) => completer,
+                    None => continue,
+                };
+
+                let candidates = line.complete(completer);
+                if !candidates.is_empty() {
+                    writeln!(writer)?;
+                    writeln!(writer, "{}", candidates.join("  "))?;
+                }
+            }
+            Key::Up | Key::Down => continue,
+            Key::PageUp | Key::PageDown | Key::Unknown => continue,
+        }
+
+        line.hint = suggest(&line, history, helpers);
+        line.draw(writer)?;
+    }
+}
+
+/// Suggests how to finish the line when the cursor is at its end: with the default if the line is empty,
+/// otherwise with the newest line in the history that starts with it, or the only completion.
+/// Returns the text to add, which is empty if there is nothing to suggest.
+fn suggest(line: &Line<'_>, history: &[String], helpers: &Helpers<'_>) -> String {
+    if line.cursor < line.chars.len() {
+        return String::new();
+    }
+
+    let text = line.text();
+
+    if text.is_empty() {
+        return helpers.default.unwrap_or_default().to_owned();
+    }
+
+    if let Some(entry) = history
+        .iter()
+        .rev()
+        .find(|entry| entry.len() > text.len() && entry.starts_with(&text))
+    {
+        return entry[text.len()..].to_owned();
+    }
+
+    if let Some(completer) = helpers.completer {
+        let Completion { start, candidates } = completer.complete(&text, text.len());
+
+        if let [candidate] = candidates.as_slice() {
+            if let Some(rest) = text
+                .get(start..)
+                .and_then(|typed| candidate.strip_prefix(typed))
+            {
+                return rest.to_owned();
+            }
+        }
+    }
+
+    String::new()
+}
+
+/// The line being edited, as characters so that the cursor never lands inside a multi-byte character.
+struct Line<'a> {
+    /// The part of the prompt on the same line as the input, which is redrawn with it.
+    prompt: &'a str,
+    chars: Vec<char>,
+    cursor: usize,
+    /// A suggestion shown after the line, which isn't part of it until it is accepted.
+    hint: String,
+    hint_style: Style,
+    color: bool,
+}
+
+impl<'a> Line<'a> {
+    fn new(prompt: &'a str, helpers: &Helpers<'_>) -> Self {
+        Line {
+            prompt: prompt.rsplit('\n').next().unwrap_or(prompt),
+            chars: Vec::new(),
+            cursor: 0,
+            hint: String::new(),
+            hint_style: helpers.hint_style,
+            color: helpers.color,
+        }
+    }
+
+    fn text(&self) -> String {
+        self.chars.iter().collect()
+    }
+
+    fn insert(&mut self, c: char) {
+        self.chars.insert(self.cursor, c);
+        self.cursor += 1;
+    }
+
+    fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            self.chars.remove(self.cursor);
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.cursor < self.chars.len() {
+            self.chars.remove(self.cursor);
+        }
+    }
+
+    fn kill_line(&mut self) {
+        self.chars.drain(..self.cursor);
+        self.cursor = 0;
+    }
+
+    fn kill_word(&mut self) {
+        let mut start = self.cursor;
+
+        while start > 0 && self.chars[start - 1].is_whitespace() {
+            start -= 1;
+        }
+        while start > 0 && !self.chars[start - 1].is_whitespace() {
+            start -= 1;
+        }
+
+        self.chars.drain(start..self.cursor);
+        self.cursor = start;
+    }
+
+    /// Fills in as much of the completion as all candidates agree on.
+    /// Returns the candidates if that doesn't add anything, so they can be listed.
+    fn complete(&mut self, completer: &dyn Completer) -> Vec<String> {
+        let text = self.text();
+        let cursor: usize = self.chars[..self.cursor].iter().map(|c| c.len_utf8()).sum();
+
+        let Completion { start, candidates } = completer.complete(&text, cursor);
+
+        if candidates.is_empty() || start > cursor || !text.is_char_boundary(start) {
+            return Vec::new();
+        }
+
+        let prefix = common_prefix(&candidates);
+
+        if candidates.len() > 1 && prefix.len() <= cursor - start {
+            return candidates;
+        }
+
+        let start = text[..start].chars().count();
+        self.chars.splice(start..self.cursor, prefix.chars());
+        self.cursor = start + prefix.chars().count();
+
+        Vec::new()
+    }
+
+    fn replace(&mut self, chars: Vec<char>) {
+        self.cursor = chars.len();
+        self.chars = chars;
+    }
+
+    /// Takes the hint off the screen, and moves on to the next line.
+    fn finish<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        if !self.hint.is_empty() {
+            self.hint.clear();
+            self.draw(writer)?;
+        }
+
+        writeln!(writer)
+    }
+
+    /// Redraws the prompt, the line and the hint, clears whatever was left of the old line,
+    /// and puts the terminal's cursor back where it belongs.
+    fn draw<W>(&self, writer: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        write!(writer, "\r{}{}", self.prompt, self.text())?;
+
+        if !self.hint.is_empty() {
+            write!(
+                writer,
+                "{}",
+                paint(&self.hint_style, &self.hint, self.color)
+            )?;
+        }
+
+        write!(writer, "\x1b[K")?;
+
+        let behind = self.chars.len() - self.cursor + self.hint.chars().count();
+        if behind > 0 {
+            write!(writer, "\x1b[{}D", behind)?;
+        }
+
+        writer.flush()
+    }
+}
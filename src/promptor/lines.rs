@@ -0,0 +1,203 @@
+//! # Lines
+//!
+//! Multi-line text input, like descriptions, commit messages or pasted certificates,
+//! read until a sentinel line, a blank line or end-of-input.
+
+use std::io::{self, BufRead, Read, Write};
+
+use super::{PromptError, Promptor};
+
+/// # Terminator
+///
+/// Says which line ends multi-line input in `Promptor::get_lines()`.
+/// The terminating line itself is not part of the result.
+/// End-of-input always ends multi-line input, whatever the terminator.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Terminator {
+    /// A line containing only this text, like `.` in mail clients.
+    Sentinel(String),
+
+    /// An empty line.
+    BlankLine,
+
+    /// Only end-of-input, e.g. Ctrl-D on a terminal.
+    Eof,
+}
+
+/// # LinesOptions
+///
+/// Says how `Promptor::get_lines_with()` knows the input is over,
+/// and how much input it accepts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinesOptions {
+    /// The line that ends the input.
+    pub terminator: Terminator,
+
+    /// The most lines to accept, not counting the terminator, if there is a limit.
+    pub max_lines: Option<usize>,
+
+    /// The most bytes to accept, counting the newlines between lines, if there is a limit.
+    pub max_bytes: Option<usize>,
+}
+
+impl LinesOptions {
+    /// Options that end the input with `terminator`, without any limits.
+    pub fn new(terminator: Terminator) -> Self {
+        LinesOptions {
+            terminator,
+            max_lines: None,
+            max_bytes: None,
+        }
+    }
+}
+
+impl<R, W> Promptor<R, W>
+where
+    R: BufRead,
+    W: Write,
+{
+    /// Gets several lines of text from the user, until the line that `terminator` describes
+    /// or end-of-input, and returns them joined with `\n`.
+    /// Only line endings are stripped; other whitespace is kept as the user typed it.
+    /// Returns `PromptError` if:
+    /// * `write!()` or `self.writer.flush()` fails
+    /// * `self.reader.read_line()` fails
+    /// * `self.reader` has reached end-of-input before the first line
+    ///
+    /// # Arguments
+    /// * `msg` – a message to display to the user before they start typing.
+    /// * `terminator` – the line that ends the input.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use promptor::Promptor;
+    /// use promptor::promptor::lines::Terminator;
+    ///
+    /// let mut promptor = Promptor::stdio();
+    ///
+    /// let description = promptor
+    ///     .get_lines(
+    ///         "Describe the change, then enter a line with a single '.':\n",
+    ///         Terminator::Sentinel(".".to_owned()),
+    ///     )
+    ///     .unwrap();
+    /// ```
+    pub fn get_lines(&mut self, msg: &str, terminator: Terminator) -> Result<String, PromptError> {
+        self.get_lines_with(msg, &LinesOptions::new(terminator))
+    }
+
+    /// Same as `get_lines()`, but also limits how many lines and bytes the user may enter.
+    /// Returns `PromptError::LimitError` as soon as the input goes over a limit,
+    /// leaving the rest of the input in `self.reader`.
+    ///
+    /// # Arguments
+    /// * `msg` – a message to display to the user before they start typing.
+    /// * `options` – the line that ends the input, and the limits.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use promptor::Promptor;
+    /// use promptor::promptor::lines::{LinesOptions, Terminator};
+    ///
+    /// let mut promptor = Promptor::stdio();
+    ///
+    /// let options = LinesOptions {
+    ///     max_bytes: Some(16 * 1024),
+    ///     ..LinesOptions::new(Terminator::Eof)
+    /// };
+    /// let certificate = promptor.get_lines_with("Paste the certificate, then press Ctrl-D:\n", &options);
+    /// ```
+    pub fn get_lines_with(
+        &mut self,
+        msg: &str,
+        options: &LinesOptions,
+    ) -> Result<String, PromptError> {
+        let text = self.theme.prompt(msg, self.color());
+        write!(&mut self.writer, "{}", text)?;
+
+        // Force output to stdout before reading from stdin
+        self.writer.flush()?;
+
+        let mut text = String::new();
+        let mut lines: usize = 0;
+        let mut buffer = Vec::new();
+
+        loop {
+            buffer.clear();
+
+            // With a byte limit, read no more than what could still fit, so that a huge line
+            // doesn't end up in memory: the room left, or the sentinel, a line ending, and one more byte.
+            let limit = options.max_bytes.map(|max_bytes| {
+                let left = max_bytes.saturating_sub(text.len() + usize::from(lines > 0));
+                let sentinel = match &options.terminator {
+                    Terminator::Sentinel(sentinel) => sentinel.len(),
+                    _ => 0,
+                };
+
+                left.max(sentinel) + 3
+            });
+
+            let read = match limit {
+                Some(limit) => (&mut self.reader)
+                    .take(limit as u64)
+                    .read_until(b'\n', &mut buffer)?,
+                None => self.reader.read_until(b'\n', &mut buffer)?,
+            };
+
+            if read == 0 {
+                if lines == 0 {
+                    return Err(PromptError::EofError);
+                }
+
+                break Ok(text);
+            }
+
+            if let (Some(limit), Some(max_bytes)) = (limit, options.max_bytes) {
+                if read == limit && !buffer.ends_with(b"\n") {
+                    return Err(PromptError::LimitError(plural(max_bytes, "byte")));
+                }
+            }
+
+            let line = std::str::from_utf8(&buffer)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?
+                .trim_end_matches(&['\r', '\n'][..]);
+
+            let terminated = match &options.terminator {
+                Terminator::Sentinel(sentinel) => line == sentinel,
+                Terminator::BlankLine => line.is_empty(),
+                Terminator::Eof => false,
+            };
+
+            if terminated {
+                break Ok(text);
+            }
+
+            lines += 1;
+
+            if let Some(max_lines) = options.max_lines {
+                if lines > max_lines {
+                    return Err(PromptError::LimitError(plural(max_lines, "line")));
+                }
+            }
+
+            if lines > 1 {
+                text.push('\n');
+            }
+            text.push_str(line);
+
+            if let Some(max_bytes) = options.max_bytes {
+                if text.len() > max_bytes {
+                    return Err(PromptError::LimitError(plural(max_bytes, "byte")));
+                }
+            }
+        }
+    }
+}
+
+fn plural(count: usize, unit: &str) -> String {
+    if count == 1 {
+        format!("1 {}", unit)
+    } else {
+        format!("{} {}s", count, unit)
+    }
+}
@@ -0,0 +1,376 @@
+//! # Menu
+//!
+//! Interactive menus, where the user moves a highlighted cursor over the options
+//! with the arrow keys, and narrows them down by typing, either to the options that contain
+//! what they typed, or to the ones that fuzzily match it.
+//! They need a terminal to draw on, and fall back to numbered menus otherwise.
+
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+
+use super::fuzzy::rank;
+use super::key::{read_key, Key};
+use super::term::raw_mode;
+use super::theme::Theme;
+use super::{PromptError, Promptor};
+
+/// The size to assume if the terminal doesn't say, as columns and rows.
+pub(crate) const DEFAULT_SIZE: (usize, usize) = (80, 24);
+
+impl<R, W> Promptor<R, W>
+where
+    R: BufRead,
+    W: Write,
+{
+    /// Shows `items` as a list under `msg`, with a highlighted cursor that the user moves
+    /// with Up/Down or j/k, Home/End and PageUp/PageDown, and picks an item with Enter.
+    /// Typing any other character, or `/` first, filters the list to the items that contain the text,
+    /// ignoring case, and Backspace edits the filter.
+    /// Lists longer than the terminal scroll. The menu is redrawn in place,
+    /// and replaced with the message and the chosen item once the user picks one,
+    /// or the theme's success line if it has one.
+    ///
+    /// This needs `self.capabilities` to be `interactive()`, like with `Promptor::stdio()`
+    /// when stdin and stdout are terminals and `TERM` isn't `dumb`.
+    /// Otherwise this function falls back to the numbered menu of `select()`.
+    ///
+    /// Returns the index of the chosen item in `items`, or `PromptError` if:
+    /// * switching the terminal to raw mode, reading keys or drawing the menu fails
+    /// * the user presses Ctrl-C, in which case the error is a `PromptError::StdinError` of kind `Interrupted`
+    /// * the user presses Ctrl-D, or `self.reader` reaches end-of-input, in which case the error is `PromptError::EofError`
+    /// * `items` is empty, in which case the error is `PromptError::NoChoices`
+    ///
+    /// # Arguments
+    /// * `msg` – a message to display to the user above the list.
+    /// * `items` – the options to choose from.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use promptor::Promptor;
+    ///
+    /// let mut promptor = Promptor::stdio();
+    ///
+    /// let regions = ["eu-west-1", "eu-central-1", "us-east-1", "us-west-2"];
+    /// let index = promptor.interactive_select("Region: ", &regions).unwrap();
+    ///
+    /// println!("Deploying to {}.", regions[index]);
+    /// ```
+    pub fn interactive_select<S>(&mut self, msg: &str, items: &[S]) -> Result<usize, PromptError>
+    where
+        S: Display,
+    {
+        if items.is_empty() {
+            return Err(PromptError::NoChoices);
+        }
+
+        if !self.capabilities.interactive() {
+            return self.select(msg, items);
+        }
+
+        let items: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+        let size = self.terminal.and_then(|terminal| terminal.size());
+
+        let choice = {
+            let _raw = raw_mode(self.terminal.as_ref())?;
+
+            let look = Look {
+                theme: &self.theme,
+                color: self.color(),
+            };
+
+            run_menu(
+                &mut self.reader,
+                &mut self.writer,
+                msg,
+                &items,
+                size.unwrap_or(DEFAULT_SIZE),
+                Filter::Substring,
+                look,
+            )?
+        };
+
+        choice.ok_or(PromptError::EofError)
+    }
+}
+
+/// How a menu narrows down its items as the user types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Filter {
+    /// Typing starts filtering, except for j, k and `/`, and keeps the items that contain the text, in order.
+    Substring,
+
+    /// Every key the user types goes into the query, which keeps the items that match it fuzzily,
+    /// best first, with the matching characters highlighted.
+    Fuzzy,
+}
+
+/// How a menu looks: the theme, and whether to use its colours.
+#[derive(Clone, Copy)]
+pub(crate) struct Look<'t> {
+    pub(crate) theme: &'t Theme,
+    pub(crate) color: bool,
+}
+
+/// Runs the menu on `reader` and `writer`, which are expected to be a terminal in raw mode
+/// of `size` columns and rows, and returns the index of the chosen item,
+/// or `None` if the user pressed Ctrl-D or the reader reached end-of-input.
+pub(crate) fn run_menu<R, W>(
+    reader: &mut R,
+    writer: &mut W,
+    msg: &str,
+    items: &[String],
+    size: (usize, usize),
+    filter: Filter,
+    look: Look<'_>,
+) -> io::Result<Option<usize>>
+where
+    R: BufRead,
+    W: Write,
+{
+    let mut menu = Menu::new(msg, items, size, filter, look);
+
+    // Hide the cursor, since the highlight shows where the user is.
+    write!(writer, "\x1b[?25l")?;
+    menu.draw(writer)?;
+
+    let choice = loop {
+        let key = match read_key(reader) {
+            Ok(Some(key)) => key,
+            Ok(None) => break Ok(None),
+            Err(err) => break Err(err),
+        };
+
+        match key {
+            Key::Enter => match menu.matches.get(menu.cursor) {
+                Some(found) => break Ok(Some(found.index)),
+                None => continue,
+            },
+            Key::EndOfFile => break Ok(None),
+            Key::Interrupt => break Err(io::Error::new(io::ErrorKind::Interrupted, "interrupted")),
+            Key::Up => menu.move_by(-1),
+            Key::Down => menu.move_by(1),
+            Key::Char('k') if !menu.filtering => menu.move_by(-1),
+            Key::Char('j') if !menu.filtering => menu.move_by(1),
+            Key::PageUp => menu.move_by(-(menu.rows() as isize)),
+            Key::PageDown => menu.move_by(menu.rows() as isize),
+            Key::Home => menu.move_by(-(items.len() as isize)),
+            Key::End => menu.move_by(items.len() as isize),
+            Key::Char('/') if !menu.filtering => menu.filtering = true,
+            Key::Char(c) => {
+                menu.filtering = true;
+                menu.filter.push(c);
+                menu.refilter();
+            }
+            Key::Backspace if menu.filtering => {
+                if menu.filter.pop().is_none() && menu.mode == Filter::Substring {
+                    menu.filtering = false;
+                }
+                menu.refilter();
+            }
+            Key::KillLine if menu.filtering => {
+                menu.filter.clear();
+                menu.refilter();
+            }
+            _ => continue,
+        }
+
+        menu.draw(writer)?;
+    };
+
+    menu.clear(writer)?;
+    if let Ok(Some(index)) = choice {
+        match look.theme.success_line(msg, &items[index], look.color) {
+            Some(line) => writeln!(writer, "{}", line)?,
+            None => writeln!(writer, "{}{}", menu.header, items[index])?,
+        }
+    }
+    write!(writer, "\x1b[?25h")?;
+    writer.flush()?;
+
+    choice
+}
+
+/// The state of an interactive menu on the screen.
+struct Menu<'a> {
+    /// The message, styled with the theme.
+    header: String,
+    items: &'a [String],
+    look: Look<'a>,
+    /// The most characters of an item that fit on a line, after the marker.
+    width: usize,
+    /// The most items that fit on the screen at once, under the message.
+    height: usize,
+    mode: Filter,
+    filter: String,
+    filtering: bool,
+    /// The items that match the filter, in the order to show them.
+    matches: Vec<Match>,
+    /// The highlighted item, as an index into `matches`.
+    cursor: usize,
+    /// The first item on the screen, as an index into `matches`.
+    top: usize,
+    /// How many lines the last frame took up.
+    drawn: usize,
+}
+
+/// An item that matches the filter.
+struct Match {
+    /// Where the item is in the list the menu was given.
+    index: usize,
+    /// The characters of the item to highlight.
+    positions: Vec<usize>,
+}
+
+impl<'a> Menu<'a> {
+    fn new(
+        msg: &str,
+        items: &'a [String],
+        (columns, rows): (usize, usize),
+        mode: Filter,
+        look: Look<'a>,
+    ) -> Self {
+        let marker = look.theme.active_marker.chars().count();
+
+        let mut menu = Menu {
+            header: look.theme.prompt(msg, look.color),
+            items,
+            look,
+            width: columns.saturating_sub(marker + 1).max(1),
+            height: rows.saturating_sub(2).max(1),
+            mode,
+            filter: String::new(),
+            filtering: mode == Filter::Fuzzy,
+            matches: Vec::new(),
+            cursor: 0,
+            top: 0,
+            drawn: 0,
+        };
+
+        menu.refilter();
+        menu
+    }
+
+    /// How many items are on the screen.
+    fn rows(&self) -> usize {
+        self.matches.len().min(self.height)
+    }
+
+    fn move_by(&mut self, offset: isize) {
+        let last = self.matches.len().saturating_sub(1) as isize;
+        self.cursor = (self.cursor as isize + offset).max(0).min(last) as usize;
+
+        if self.cursor < self.top {
+            self.top = self.cursor;
+        } else if self.cursor >= self.top + self.height {
+            self.top = self.cursor + 1 - self.height;
+        }
+    }
+
+    fn refilter(&mut self) {
+        self.matches = match self.mode {
+            Filter::Substring => {
+                let filter = self.filter.to_lowercase();
+
+                (0..self.items.len())
+                    .filter(|&index| self.items[index].to_lowercase().contains(&filter))
+                    .map(|index| Match {
+                        index,
+                        positions: Vec::new(),
+                    })
+                    .collect()
+            }
+            Filter::Fuzzy => rank(self.items, &self.filter)
+                .into_iter()
+                .map(|(index, positions)| Match { index, positions })
+                .collect(),
+        };
+        self.cursor = 0;
+        self.top = 0;
+    }
+
+    /// Draws the message, the filter and the items on the screen, over the last frame.
+    fn draw<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        self.move_to_top(writer)?;
+
+        if self.mode == Filter::Fuzzy {
+            writeln!(writer, "{}{}\x1b[K", self.header, self.filter)?;
+        } else if self.filtering {
+            writeln!(writer, "{}/{}\x1b[K", self.header, self.filter)?;
+        } else {
+            writeln!(writer, "{}\x1b[K", self.header)?;
+        }
+
+        let theme = self.look.theme;
+
+        let visible = &self.matches[self.top..self.top + self.rows()];
+
+        for (row, found) in visible.iter().enumerate() {
+            let item = highlight(&self.items[found.index], &found.positions, self.width);
+
+            if self.top + row == self.cursor {
+                writeln!(
+                    writer,
+                    "\x1b[7m{}{}\x1b[0m\x1b[K",
+                    theme.active_marker, item
+                )?;
+            } else {
+                writeln!(writer, "{}{}\x1b[K", theme.inactive_marker, item)?;
+            }
+        }
+
+        if visible.is_empty() {
+            writeln!(writer, "{}No matches.\x1b[K", theme.inactive_marker)?;
+        }
+
+        // Clear what's left of a longer frame.
+        write!(writer, "\x1b[J")?;
+
+        self.drawn = 1 + visible.len().max(1);
+
+        writer.flush()
+    }
+
+    /// Takes the menu off the screen.
+    fn clear<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        self.move_to_top(writer)?;
+        write!(writer, "\x1b[J")?;
+        self.drawn = 0;
+
+        Ok(())
+    }
+
+    /// Moves the terminal's cursor back to the start of the last frame.
+    fn move_to_top<W>(&self, writer: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        if self.drawn > 0 {
+            write!(writer, "\x1b[{}A", self.drawn)?;
+        }
+
+        write!(writer, "\r")
+    }
+}
+
+/// Cuts `item` down to `width` characters, and makes the characters at `positions` bold.
+fn highlight(item: &str, positions: &[usize], width: usize) -> String {
+    let mut highlighted = String::new();
+
+    for (position, c) in item.chars().take(width).enumerate() {
+        if positions.contains(&position) {
+            highlighted.push_str("\x1b[1m");
+            highlighted.push(c);
+            highlighted.push_str("\x1b[22m");
+        } else {
+            highlighted.push(c);
+        }
+    }
+
+    highlighted
+}
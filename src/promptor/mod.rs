@@ -4,39 +4,149 @@
 //! to hold the handles to  your input/output streams,
 //! and then you can call the methods on that object to get input from that stream.
 
-use std::io::{BufRead, Write};
+use std::io::{BufRead, StdinLock, Stdout, Write};
 
 use thiserror::Error;
 
+pub mod capabilities;
+pub mod completer;
+pub mod confirm;
+pub mod edit;
+pub mod fuzzy;
+pub mod history;
+mod key;
+pub mod line_editor;
+pub mod lines;
+pub mod menu;
+pub mod password;
+pub mod prompt;
+pub mod retry;
+pub mod secret;
+pub mod select;
+pub mod term;
+pub mod testing;
+pub mod theme;
+pub mod validator;
+
+use self::capabilities::Capabilities;
+use self::history::HistoryStore;
+use self::line_editor::{Helpers, LineEditor};
+use self::prompt::Prompt;
+use self::retry::{rejection, RetryPolicy, INVALID_INPUT};
+use self::term::{raw_mode, Terminal};
+use self::theme::Theme;
+use self::validator::Validator;
+
+/// # BoxedError
+///
+/// The type-erased error that `PromptError::ReadError` keeps from `T::from_str()`.
+pub type BoxedError = Box<dyn std::error::Error + Send + Sync>;
+
 /// # SafeParsable
 ///
-/// Defines a trait that is safe to parse from a string and has a default value
-/// for `.unwrap_or_default()`
-pub trait SafeParsable: Sized + Copy + Default + std::str::FromStr {}
+/// Defines a trait that is safe to parse from a string.
+/// Any type that implements `std::str::FromStr` with a reasonable error type qualifies,
+/// including types that own heap data, like `String`, `PathBuf` and `Vec`-backed newtypes.
+/// `ParseError` names the `std::str::FromStr` error so that it can be kept in `PromptError::ReadError`.
+pub trait SafeParsable:
+    Sized + std::str::FromStr<Err = <Self as SafeParsable>::ParseError>
+{
+    type ParseError: Into<BoxedError>;
+}
 
-impl<T> SafeParsable for T where T: Sized + Copy + Default + std::str::FromStr {}
+impl<T> SafeParsable for T
+where
+    T: Sized + std::str::FromStr,
+    T::Err: Into<BoxedError>,
+{
+    type ParseError = T::Err;
+}
 
 /// # Promptor
 ///
 /// Holds the input and output handles and redirects input and output to them.
+/// If the reader is attached to a terminal, `terminal` lets prompts like `password()`
+/// control it; otherwise they fall back to plain line-based input.
+/// `capabilities` says what the streams support, which the constructors find out once;
+/// if you change `terminal` afterwards, update it with `Capabilities::detect()`.
+/// With a terminal, setting `line_editor` lets the user edit their answers and recall earlier ones,
+/// and setting `history` also keeps the answers to prompts that opt in with `Prompt::history()`.
+///
+/// When the user's input is rejected, prompts tell them why and ask again.
+/// Set `retry_message` to say it your own way, with `{input}` standing for what the user entered
+/// and `{reason}` for why it was rejected, and `error_writer` to say it somewhere else, like stderr.
+/// Every prompt that asks again uses them, including `confirm()`, `select()` and `multi_select()`,
+/// whose built-in messages become the `{reason}`; for `password_confirmed()`, `{input}` is always empty.
+///
+/// `theme` decides how prompts look. The default theme shows messages exactly as given;
+/// see `Theme::colorful()` for one with colours, which are only used on a terminal.
 ///
 /// # Example
 /// To use this with stdio:
 /// ```
 /// use promptor::Promptor;
 ///
-/// let stdio = std::io::stdin();
-/// let input = stdio.lock();
-/// let output = std::io::stdout();
+/// let mut promptor = Promptor::stdio();
+/// ```
+/// Or with any other pair of handles:
+/// ```
+/// use promptor::Promptor;
 ///
-/// let mut promptor = Promptor {
-///     reader: input,
-///     writer: output
-/// };
+/// let input = b"32";
+/// let mut output: Vec<u8> = Vec::new();
+///
+/// let mut promptor = Promptor::new(&input[..], &mut output);
 /// ```
 pub struct Promptor<R, W> {
     pub reader: R,
     pub writer: W,
+    pub terminal: Option<Terminal>,
+    pub capabilities: Capabilities,
+    pub line_editor: Option<LineEditor>,
+    pub history: Option<HistoryStore>,
+    pub retry_message: Option<String>,
+    pub error_writer: Option<Box<dyn Write + Send>>,
+    pub theme: Theme,
+}
+
+impl<R, W> Promptor<R, W> {
+    /// Creates a `Promptor` that reads from `reader` and writes to `writer`,
+    /// without a terminal, so prompts use plain line-based input and output unless you set `capabilities`.
+    ///
+    /// # Arguments
+    /// * `reader` – the input handle, e.g. `std::io::stdin().lock()` or a `&[u8]`.
+    /// * `writer` – the output handle, e.g. `std::io::stdout()` or a `Vec<u8>`.
+    pub fn new(reader: R, writer: W) -> Self {
+        Promptor {
+            reader,
+            writer,
+            terminal: None,
+            capabilities: Capabilities::detect(None),
+            line_editor: None,
+            history: None,
+            retry_message: None,
+            error_writer: None,
+            theme: Theme::default(),
+        }
+    }
+}
+
+impl Promptor<StdinLock<'static>, Stdout> {
+    /// Creates a `Promptor` that reads from stdin and writes to stdout,
+    /// using the terminal attached to stdin, if there is one, and noting whether stdout is a terminal.
+    pub fn stdio() -> Self {
+        Promptor {
+            reader: std::io::stdin().lock(),
+            writer: std::io::stdout(),
+            terminal: Terminal::stdio(),
+            capabilities: Capabilities::stdio(),
+            line_editor: None,
+            history: None,
+            retry_message: None,
+            error_writer: None,
+            theme: Theme::default(),
+        }
+    }
 }
 
 /// # PromptError
@@ -53,8 +163,56 @@ pub enum PromptError {
     /// ### ReadError
     ///
     /// Throws in the event that `read()` fails.
-    #[error("Failure converting string to data type")]
-    ReadError,
+    /// Holds the text that failed to convert and the error that `T::from_str()` returned.
+    #[error("Failure converting {input:?} to data type: {source}")]
+    ReadError { input: String, source: BoxedError },
+
+    /// ### ValidationError
+    ///
+    /// Throws in the event that a validator rejects the user's input.
+    /// Holds the validator's message.
+    #[error("Validation failed: {0}")]
+    ValidationError(String),
+
+    /// ### TooManyAttempts
+    ///
+    /// Throws in the event that the user runs out of attempts allowed by a `RetryPolicy`.
+    /// Holds the number of attempts and the error that ended the last one.
+    #[error("Gave up after {attempts} attempts: {last}")]
+    TooManyAttempts {
+        attempts: u32,
+        #[source]
+        last: Box<PromptError>,
+    },
+
+    /// ### LimitError
+    ///
+    /// Throws in the event that multi-line input goes over a line or size limit.
+    /// Holds the limit, e.g. `"100 lines"`.
+    #[error("Input went over the limit of {0}")]
+    LimitError(String),
+
+    /// ### EditorError
+    ///
+    /// Throws in the event that the editor in `edit()` can't be started or exits unsuccessfully.
+    /// Holds the editor command and what went wrong.
+    #[error("Editor {editor:?} failed: {reason}")]
+    EditorError { editor: String, reason: String },
+
+    /// ### EofError
+    ///
+    /// Throws in the event that the reader reaches end-of-input
+    /// before the user has entered a line.
+    #[error("Reached end of input")]
+    EofError,
+
+    /// ### NoChoices
+    ///
+    /// Throws in the event that a menu like `select()` is given no items to choose from,
+    /// or limits like `MultiSelectOptions::min` that no choice of its items could meet,
+    /// since the user could never pick anything.
+    #[error("There is nothing to choose from")]
+    NoChoices,
 }
 
 impl<R, W> Promptor<R, W>
@@ -63,8 +221,9 @@ where
     W: Write,
 {
     /// Get a newline-terminated string from stdin,
-    /// returning `None` if `std::io::stdout.flush()` fails
-    /// or if `std::io::stdin().read_line()` fails.
+    /// returning `None` if `std::io::stdout.flush()` fails,
+    /// if `std::io::stdin().read_line()` fails,
+    /// or if stdin has reached end-of-input.
     ///
     /// # Arguments
     /// * `msg` – a message to display to the user.
@@ -77,10 +236,7 @@ where
     /// let input = stdio.lock();
     /// let output = std::io::stdout();
     ///
-    /// let mut promptor = Promptor {
-    ///     reader: input,
-    ///     writer: output
-    /// };
+    /// let mut promptor = Promptor::new(input, output);
     ///
     /// let res = promptor.get_line("What's your name?");
     ///
@@ -99,7 +255,13 @@ where
     ///     getLine
     /// ```
     pub fn get_line(&mut self, msg: &str) -> Option<String> {
-        match write!(&mut self.writer, "{}", msg) {
+        if self.line_editor.is_some() && self.capabilities.interactive() {
+            return self.rget_line(msg).ok();
+        }
+
+        let text = self.theme.prompt(msg, self.color());
+
+        match write!(&mut self.writer, "{}", text) {
             Ok(()) => (),
             Err(_) => return None,
         }
@@ -113,6 +275,7 @@ where
         let mut buffer: String = String::new();
 
         match self.reader.read_line(&mut buffer) {
+            Ok(0) => return None,
             Ok(_) => (),
             Err(_) => return None,
         }
@@ -127,6 +290,12 @@ where
     /// * `self.writer.flush()` fails
     /// * `self.reader.read_line()` fails
     ///
+    /// Returns `PromptError::EofError` if `self.reader` has reached end-of-input.
+    ///
+    /// If `self.line_editor` is set and `self.capabilities` is `interactive()`, the line is read in raw mode
+    /// with the line editor, and the user pressing Ctrl-C is a `PromptError::StdinError`
+    /// of kind `Interrupted`.
+    ///
     /// # Arguments
     /// * `msg` – a message to display to the user.
     ///
@@ -138,10 +307,7 @@ where
     /// let input = stdio.lock();
     /// let output = std::io::stdout();
     ///
-    /// let mut promptor = Promptor {
-    ///     reader: input,
-    ///     writer: output
-    /// };
+    /// let mut promptor = Promptor::new(input, output);
     ///
     /// let res = promptor.rget_line("What's your name?");
     ///
@@ -151,18 +317,61 @@ where
     /// }
     /// ```
     pub fn rget_line(&mut self, msg: &str) -> Result<String, PromptError> {
-        write!(&mut self.writer, "{}", msg).map_err(|err| PromptError::StdinError(err))?;
+        self.rget_line_with(msg, Helpers::default())
+    }
+
+    /// Same as `rget_line()`, but if the line editor is in use, it gets `helpers` from the prompt,
+    /// like its own history and a completer.
+    pub(crate) fn rget_line_with(
+        &mut self,
+        msg: &str,
+        helpers: Helpers<'_>,
+    ) -> Result<String, PromptError> {
+        let text = self.theme.prompt(msg, self.color());
+
+        self.read_line_as(&text, helpers)
+    }
+
+    /// Same as `rget_line_with()`, but shows `text` exactly as given, for prompts that style it themselves.
+    pub(crate) fn read_line_as(
+        &mut self,
+        text: &str,
+        helpers: Helpers<'_>,
+    ) -> Result<String, PromptError> {
+        let helpers = Helpers {
+            hint_style: self.theme.default,
+            color: self.color(),
+            ..helpers
+        };
+
+        if let (true, Some(editor)) = (self.capabilities.interactive(), &mut self.line_editor) {
+            let line = {
+                let _raw = raw_mode(self.terminal.as_ref())?;
+
+                editor.read_line_with(&mut self.reader, &mut self.writer, text, helpers)?
+            };
+
+            return match line {
+                Some(line) => Ok(line.trim_end().to_owned()),
+                None => Err(PromptError::EofError),
+            };
+        }
+
+        write!(&mut self.writer, "{}", text).map_err(PromptError::StdinError)?;
 
         // Force output to stdout before reading from stdin
-        self.writer
-            .flush()
-            .map_err(|err| PromptError::StdinError(err))?;
+        self.writer.flush().map_err(PromptError::StdinError)?;
 
         let mut buffer: String = String::new();
 
-        self.reader
+        let read = self
+            .reader
             .read_line(&mut buffer)
-            .map_err(|err| PromptError::StdinError(err))?;
+            .map_err(PromptError::StdinError)?;
+
+        if read == 0 {
+            return Err(PromptError::EofError);
+        }
 
         Ok(buffer.trim_end().to_owned())
     }
@@ -183,10 +392,7 @@ where
     /// let input = stdio.lock();
     /// let output = std::io::stdout();
     ///
-    /// let mut promptor = Promptor {
-    ///     reader: input,
-    ///     writer: output
-    /// };
+    /// let mut promptor = Promptor::new(input, output);
     ///
     /// let res = promptor.read::<i32>("32").map(|x| x * 2).unwrap();
     ///
@@ -201,15 +407,12 @@ where
     where
         T: std::str::FromStr,
     {
-        match T::from_str(arg) {
-            Ok(res) => Some(res),
-            Err(_) => None,
-        }
+        T::from_str(arg).ok()
     }
 
     /// Same as `read()`, but returns a `Result<T, PromptError>`.
     /// Use this version if you need control over the errors.
-    /// Returns `PromptError::ReadError` if:
+    /// Returns `PromptError::ReadError`, holding `arg` and the error from `T::from_str()`, if:
     /// * `T::from_str(arg)` fails
     ///
     /// # Arguments
@@ -223,10 +426,7 @@ where
     /// let input = stdio.lock();
     /// let output = std::io::stdout();
     ///
-    /// let mut promptor = Promptor {
-    ///     reader: input,
-    ///     writer: output
-    /// };
+    /// let mut promptor = Promptor::new(input, output);
     ///
     /// let res = promptor.rread::<i32>("32").map(|x| x * 2).unwrap();
     ///
@@ -235,12 +435,15 @@ where
     pub fn rread<T>(&mut self, arg: &str) -> Result<T, PromptError>
     where
         T: std::str::FromStr,
+        T::Err: Into<BoxedError>,
     {
-        Ok(T::from_str(arg).map_err(|_| PromptError::ReadError)?)
+        T::from_str(arg).map_err(|err| PromptError::ReadError {
+            input: arg.to_owned(),
+            source: err.into(),
+        })
     }
 
-    /// Gets a value of type `T` from the user, where `T` defines a default value
-    /// and implements `std::str::FromStr`.
+    /// Gets a value of type `T` from the user, where `T` implements `std::str::FromStr`.
     /// This function returns `None` if it is not able to parse the user's input into `T`.
     ///
     /// # Arguments
@@ -254,10 +457,7 @@ where
     /// let input = stdio.lock();
     /// let output = std::io::stdout();
     ///
-    /// let mut promptor = Promptor {
-    ///     reader: input,
-    ///     writer: output
-    /// };
+    /// let mut promptor = Promptor::new(input, output);
     ///
     /// let res = promptor.input::<i32>("Please enter a number: ");
     ///
@@ -285,7 +485,7 @@ where
     /// Same as `input()`, but returns a `Result<T, PromptError>`.
     /// Use this version if you need control over the errors.
     /// Returns `PromptError` if:
-    /// * `rget_line()` fails
+    /// * `rget_line()` fails, including when `self.reader` has reached end-of-input
     /// * `rread()` fails
     ///
     /// # Arguments
@@ -299,10 +499,7 @@ where
     /// let input = stdio.lock();
     /// let output = std::io::stdout();
     ///
-    /// let mut promptor = Promptor {
-    ///     reader: input,
-    ///     writer: output
-    /// };
+    /// let mut promptor = Promptor::new(input, output);
     ///
     /// let res = promptor.rinput::<i32>("Please enter a number: ");
     ///
@@ -326,45 +523,47 @@ where
     /// * `validator` – a function which immutably borrows a single argument of type `T` and returns a `bool`.
     ///
     /// # Example
-    /// ```
+    /// ```no_run
     /// use promptor::Promptor;
     ///
     /// let stdio = std::io::stdin();
     /// let input = stdio.lock();
     /// let output = std::io::stdout();
     ///
-    /// let mut promptor = Promptor {
-    ///     reader: input,
-    ///     writer: output
-    /// };
+    /// let mut promptor = Promptor::new(input, output);
     ///
-    /// let res: u32 = promptor.prompt("Please enter a number between 1 and 100: ", |x| 1 <= x && x <= 100);
+    /// let res: u32 = promptor.prompt("Please enter a number between 1 and 100: ", |&x| 1 <= x && x <= 100);
     /// ```
+    ///
+    /// # Panics
+    /// If the reader reaches end-of-input before the user enters valid input,
+    /// this function panics with the message:
+    /// `"reached end of input before getting valid input"`
+    ///
+    /// If reading fails, e.g. when the user presses Ctrl-C in the line editor,
+    /// this function panics with the error instead of asking again.
+    ///
+    /// Use `try_prompt()` if you need to handle end-of-input and other errors yourself.
     pub fn prompt<T, F>(&mut self, msg: &str, validator: F) -> T
     where
         T: SafeParsable,
-        F: Fn(T) -> bool,
+        F: Fn(&T) -> bool,
     {
         loop {
-            let res: T = match self.input::<T>(msg) {
-                Some(val) => val,
-                None => {
-                    match writeln!(&mut self.writer, "Invalid input! Please try again.") {
-                        Ok(()) => (),
-                        Err(_) => (),
-                    }
-                    continue;
+            let (line, error) = match self.rget_line(msg) {
+                Ok(line) => match self.rread::<T>(&line) {
+                    Ok(val) if validator(&val) => break val,
+                    Ok(_) => (line, None),
+                    Err(e) => (line, Some(e)),
+                },
+                Err(PromptError::EofError) => {
+                    panic!("reached end of input before getting valid input")
                 }
+                Err(e) => panic!("failed to prompt for input: {}", e),
             };
 
-            if validator(res) {
-                break res;
-            } else {
-                match writeln!(&mut self.writer, "Invalid input! Please try again.") {
-                    Ok(()) => (),
-                    Err(_) => (),
-                }
-            }
+            let reason = error.map_or_else(|| INVALID_INPUT.to_owned(), |e| rejection(&e).0);
+            let _ = self.show_retry(None, &line, &reason, INVALID_INPUT);
         }
     }
 
@@ -372,6 +571,8 @@ where
     /// This function is essentially the same as the `Option` version,
     /// but I have added it for completeness, and in case the emitted `Result`s
     /// are more useful for debugging.
+    /// Unlike `prompt()`, it tells the user why their input could not be converted,
+    /// e.g. `"Invalid input: number too large to fit in target type. Please try again."`.
     ///
     /// **Warning**: this function will panic if `writeln()` fails when:
     /// * `write!()` succeeds in `rget_line()` but `writeln!()` fails in this function.
@@ -382,19 +583,16 @@ where
     /// * `validator` – a function which immutably borrows a single argument of type `T` and returns a `bool`.
     ///
     /// # Example
-    /// ```
+    /// ```no_run
     /// use promptor::Promptor;
     ///
     /// let stdio = std::io::stdin();
     /// let input = stdio.lock();
     /// let output = std::io::stdout();
     ///
-    /// let mut promptor = Promptor {
-    ///     reader: input,
-    ///     writer: output
-    /// };
+    /// let mut promptor = Promptor::new(input, output);
     ///
-    /// let res: u32 = promptor.rprompt("Please enter a number between 1 and 100: ", |x| 1 <= x && x <= 100);
+    /// let res: u32 = promptor.rprompt("Please enter a number between 1 and 100: ", |&x| 1 <= x && x <= 100);
     /// ```
     ///
     /// # Panics
@@ -402,34 +600,339 @@ where
     /// this function panics with the message:
     /// `"writeln!() failed, even though write!() succeeded earlier"`
     ///
-    /// I
+    /// If the reader reaches end-of-input before the user enters valid input,
+    /// this function panics with the message:
+    /// `"reached end of input before getting valid input"`
+    ///
+    /// If reading fails, e.g. when the user presses Ctrl-C in the line editor,
+    /// this function panics with the error instead of asking again.
     pub fn rprompt<T, F>(&mut self, msg: &str, validator: F) -> T
     where
         T: SafeParsable,
-        F: Fn(T) -> bool,
+        F: Fn(&T) -> bool,
     {
         loop {
-            let res: T = match self.rinput::<T>(msg) {
-                Ok(val) => val,
-                Err(_) => {
-                    match writeln!(&mut self.writer, "Invalid input! Please try again.") {
-                        Ok(()) => (),
-                        Err(_) => {
-                            panic!("writeln!() failed, even though write!() succeeded earlier")
-                        }
-                    }
-                    continue;
+            let (line, (reason, default)) = match self.rget_line(msg) {
+                Ok(line) => match self.rread::<T>(&line) {
+                    Ok(val) if validator(&val) => break val,
+                    Ok(_) => (line, (INVALID_INPUT.to_owned(), INVALID_INPUT.to_owned())),
+                    Err(e) => (line, rejection(&e)),
+                },
+                Err(PromptError::EofError) => {
+                    panic!("reached end of input before getting valid input")
                 }
+                Err(e) => panic!("failed to prompt for input: {}", e),
             };
 
-            if validator(res) {
-                break res;
+            if self.show_retry(None, &line, &reason, &default).is_err() {
+                panic!("writeln!() failed, even though write!() succeeded earlier")
+            }
+        }
+    }
+
+    /// Same as `prompt()`, but returns a `Result<T, PromptError>` instead of
+    /// looping or panicking when something goes wrong with the streams.
+    /// Invalid input is still met with a re-prompt, which tells the user why their input
+    /// could not be converted, just like `rprompt()`.
+    /// Returns `PromptError` if:
+    /// * `rget_line()` fails, including when `self.reader` has reached end-of-input
+    /// * `writeln!()` fails while asking the user to try again
+    ///
+    /// # Arguments
+    /// * `msg` – a message to display to the user.
+    /// * `validator` – a function which immutably borrows a single argument of type `T` and returns a `bool`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use promptor::Promptor;
+    /// use promptor::promptor::PromptError;
+    ///
+    /// let stdio = std::io::stdin();
+    /// let input = stdio.lock();
+    /// let output = std::io::stdout();
+    ///
+    /// let mut promptor = Promptor::new(input, output);
+    ///
+    /// match promptor.try_prompt::<u32, _>("Please enter a number between 1 and 100: ", |&x| 1 <= x && x <= 100) {
+    ///     Ok(x) => println!("Got {}.", x),
+    ///     Err(PromptError::EofError) => eprintln!("No more input, aborting."),
+    ///     Err(e) => eprintln!("I'm sorry! I got an error: {}", e)
+    /// }
+    /// ```
+    pub fn try_prompt<T, F>(&mut self, msg: &str, validator: F) -> Result<T, PromptError>
+    where
+        T: SafeParsable,
+        F: Fn(&T) -> bool,
+    {
+        self.try_prompt_with(msg, |x: &T| {
+            if validator(x) {
+                Ok(())
             } else {
-                match writeln!(&mut self.writer, "Invalid input! Please try again.") {
-                    Ok(()) => (),
-                    Err(_) => panic!("writeln!() failed, even though write!() succeeded earlier"),
+                Err(INVALID_INPUT.to_owned())
+            }
+        })
+    }
+
+    /// Prompts the user for a value of type `T` and validates it against `validator`,
+    /// which explains why it rejected a value instead of just saying no.
+    /// If input or validation fails, this function shows the user the reason and re-prompts them.
+    ///
+    /// # Arguments
+    /// * `msg` – a message to display to the user.
+    /// * `validator` – a `Validator<T>`, or a function which immutably borrows a single argument of type `T`
+    ///   and returns `Ok(())` if the value is acceptable, or `Err` with a message for the user if it is not.
+    ///   See the `validator` module for ready-made validators.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use promptor::Promptor;
+    ///
+    /// let stdio = std::io::stdin();
+    /// let input = stdio.lock();
+    /// let output = std::io::stdout();
+    ///
+    /// let mut promptor = Promptor::new(input, output);
+    ///
+    /// let res: u32 = promptor.prompt_with("Please enter a number between 1 and 100: ", |x: &u32| {
+    ///     if 1 <= *x && *x <= 100 {
+    ///         Ok(())
+    ///     } else {
+    ///         Err("The number must be between 1 and 100.".to_owned())
+    ///     }
+    /// });
+    /// ```
+    ///
+    /// # Panics
+    /// If the reader reaches end-of-input before the user enters valid input,
+    /// this function panics with the message:
+    /// `"reached end of input before getting valid input"`
+    ///
+    /// If reading fails, e.g. when the user presses Ctrl-C in the line editor,
+    /// this function panics with the error instead of asking again.
+    ///
+    /// Use `try_prompt_with()` if you need to handle end-of-input and other errors yourself.
+    pub fn prompt_with<T, V>(&mut self, msg: &str, validator: V) -> T
+    where
+        T: SafeParsable,
+        V: Validator<T>,
+    {
+        loop {
+            let (line, (reason, default)) = match self.rget_line(msg) {
+                Ok(line) => match self.rread::<T>(&line) {
+                    Ok(val) => match validator.validate(&val) {
+                        Ok(()) => break val,
+                        Err(reason) => (line, (reason.clone(), reason)),
+                    },
+                    Err(e) => (line, rejection(&e)),
+                },
+                Err(PromptError::EofError) => {
+                    panic!("reached end of input before getting valid input")
                 }
+                Err(e) => panic!("failed to prompt for input: {}", e),
+            };
+
+            let _ = self.show_retry(None, &line, &reason, &default);
+        }
+    }
+
+    /// Same as `prompt_with()`, but returns a `Result<T, PromptError>` instead of
+    /// looping or panicking when something goes wrong with the streams.
+    /// Returns `PromptError` if:
+    /// * `rget_line()` fails, including when `self.reader` has reached end-of-input
+    /// * `writeln!()` fails while asking the user to try again
+    ///
+    /// # Arguments
+    /// * `msg` – a message to display to the user.
+    /// * `validator` – a `Validator<T>`, or a function which immutably borrows a single argument of type `T`
+    ///   and returns `Ok(())` if the value is acceptable, or `Err` with a message for the user if it is not.
+    ///   See the `validator` module for ready-made validators.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use promptor::Promptor;
+    /// use promptor::validator::{range, Validator};
+    ///
+    /// let stdio = std::io::stdin();
+    /// let input = stdio.lock();
+    /// let output = std::io::stdout();
+    ///
+    /// let mut promptor = Promptor::new(input, output);
+    ///
+    /// let res = promptor.try_prompt_with::<u32, _>(
+    ///     "Please enter a port number: ",
+    ///     range(1..=65535).and(range(..6000).not("Ports 0-5999 are reserved.")),
+    /// );
+    /// ```
+    pub fn try_prompt_with<T, V>(&mut self, msg: &str, validator: V) -> Result<T, PromptError>
+    where
+        T: SafeParsable,
+        V: Validator<T>,
+    {
+        self.try_prompt_with_policy(msg, validator, &RetryPolicy::default())
+    }
+
+    /// Same as `try_prompt_with()`, but gives up once the user has used up the attempts
+    /// that `policy` allows, waiting between attempts if `policy` asks for it.
+    /// Returns `PromptError` if:
+    /// * `rget_line()` fails, including when `self.reader` has reached end-of-input
+    /// * `writeln!()` fails while asking the user to try again
+    /// * the user runs out of attempts, in which case the error is `PromptError::TooManyAttempts`,
+    ///   holding the `PromptError::ReadError` or `PromptError::ValidationError` of the last attempt
+    ///
+    /// # Arguments
+    /// * `msg` – a message to display to the user.
+    /// * `validator` – a `Validator<T>`, or a function which immutably borrows a single argument of type `T`
+    ///   and returns `Ok(())` if the value is acceptable, or `Err` with a message for the user if it is not.
+    /// * `policy` – how many attempts the user gets, and how long to wait between them.
+    ///
+    /// # Example
+    /// Fall back to a default if the user can't come up with a valid number in three attempts:
+    /// ```no_run
+    /// use promptor::Promptor;
+    /// use promptor::promptor::retry::RetryPolicy;
+    /// use promptor::validator::range;
+    ///
+    /// let stdio = std::io::stdin();
+    /// let input = stdio.lock();
+    /// let output = std::io::stdout();
+    ///
+    /// let mut promptor = Promptor::new(input, output);
+    ///
+    /// let volume: u32 = promptor
+    ///     .try_prompt_with_policy("Volume: ", range(0..=11), &RetryPolicy::attempts(3))
+    ///     .unwrap_or(5);
+    /// ```
+    pub fn try_prompt_with_policy<T, V>(
+        &mut self,
+        msg: &str,
+        validator: V,
+        policy: &RetryPolicy,
+    ) -> Result<T, PromptError>
+    where
+        T: SafeParsable,
+        V: Validator<T>,
+    {
+        let text = self.theme.prompt(msg, self.color());
+
+        Prompt::new(self, msg)
+            .validate(validator)
+            .policy(policy.clone())
+            .run_as(&text)
+    }
+
+    /// Prompts the user for a value of type `T`, offering `default` if they just press Enter.
+    /// The default is shown after `msg`, so `"Port"` with a default of `8080` becomes `Port [8080]: `.
+    /// If input or validation fails, this function shows the user the reason and re-prompts them.
+    /// `default` itself is not validated.
+    ///
+    /// # Arguments
+    /// * `msg` – a message to display to the user, without a trailing `": "`.
+    /// * `default` – the value to return if the user enters an empty line.
+    /// * `validator` – a `Validator<T>`, or a function which immutably borrows a single argument of type `T`
+    ///   and returns `Ok(())` if the value is acceptable, or `Err` with a message for the user if it is not.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use promptor::Promptor;
+    /// use promptor::validator::range;
+    ///
+    /// let stdio = std::io::stdin();
+    /// let input = stdio.lock();
+    /// let output = std::io::stdout();
+    ///
+    /// let mut promptor = Promptor::new(input, output);
+    ///
+    /// let port: u16 = promptor.prompt_with_default("Port", 8080, range(1..));
+    /// ```
+    ///
+    /// # Panics
+    /// If the reader reaches end-of-input before the user enters valid input,
+    /// this function panics with the message:
+    /// `"reached end of input before getting valid input"`
+    ///
+    /// If reading or writing fails, this function panics with the error.
+    /// Use `try_prompt_with_default()` if you need to handle these errors yourself.
+    pub fn prompt_with_default<T, V>(&mut self, msg: &str, default: T, validator: V) -> T
+    where
+        T: SafeParsable + std::fmt::Display,
+        V: Validator<T>,
+    {
+        match self.try_prompt_with_default(msg, default, validator) {
+            Ok(val) => val,
+            Err(PromptError::EofError) => {
+                panic!("reached end of input before getting valid input")
             }
+            Err(e) => panic!("failed to prompt for input: {}", e),
         }
     }
+
+    /// Same as `prompt_with_default()`, but returns a `Result<T, PromptError>` instead of
+    /// panicking when something goes wrong with the streams.
+    /// Returns `PromptError` if:
+    /// * `rget_line()` fails, including when `self.reader` has reached end-of-input
+    /// * `writeln!()` fails while asking the user to try again
+    ///
+    /// # Arguments
+    /// * `msg` – a message to display to the user, without a trailing `": "`.
+    /// * `default` – the value to return if the user enters an empty line.
+    /// * `validator` – a `Validator<T>`, or a function which immutably borrows a single argument of type `T`
+    ///   and returns `Ok(())` if the value is acceptable, or `Err` with a message for the user if it is not.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use promptor::Promptor;
+    /// use promptor::validator::non_empty;
+    ///
+    /// let stdio = std::io::stdin();
+    /// let input = stdio.lock();
+    /// let output = std::io::stdout();
+    ///
+    /// let mut promptor = Promptor::new(input, output);
+    ///
+    /// let host = promptor.try_prompt_with_default("Host", "localhost".to_owned(), non_empty());
+    /// ```
+    pub fn try_prompt_with_default<T, V>(
+        &mut self,
+        msg: &str,
+        default: T,
+        validator: V,
+    ) -> Result<T, PromptError>
+    where
+        T: SafeParsable + std::fmt::Display,
+        V: Validator<T>,
+    {
+        self.ask(msg).default(default).validate(validator).run()
+    }
+
+    /// Starts building a prompt for a value of type `T`, for when you need more options
+    /// than the other prompt methods take, like a default, several validators, a retry limit,
+    /// help text or a custom error message. Call `run()` on the result to ask the user.
+    ///
+    /// # Arguments
+    /// * `msg` – a message to display to the user, without a trailing `": "`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use promptor::Promptor;
+    /// use promptor::validator::range;
+    ///
+    /// let stdio = std::io::stdin();
+    /// let input = stdio.lock();
+    /// let output = std::io::stdout();
+    ///
+    /// let mut promptor = Promptor::new(input, output);
+    ///
+    /// let port = promptor
+    ///     .ask::<u32>("Port")
+    ///     .default(8080)
+    ///     .validate(range(1..=65535))
+    ///     .retries(3)
+    ///     .run();
+    /// ```
+    pub fn ask<T>(&mut self, msg: &str) -> Prompt<'_, T, R, W>
+    where
+        T: SafeParsable,
+    {
+        Prompt::new(self, msg)
+    }
 }
@@ -0,0 +1,181 @@
+//! # Password
+//!
+//! Prompts for passwords, API tokens and other secrets, which the terminal shouldn't echo,
+//! and which shouldn't linger in memory.
+
+use std::io::{self, BufRead, Write};
+
+use zeroize::Zeroizing;
+
+use super::secret::SecretString;
+use super::term::Terminal;
+use super::{PromptError, Promptor};
+
+/// How much room to make for a secret to begin with. The buffer grows as needed.
+const INITIAL_CAPACITY: usize = 64;
+
+impl<R, W> Promptor<R, W>
+where
+    R: BufRead,
+    W: Write,
+{
+    /// Gets a secret from the user without echoing it, if `self.terminal` is set
+    /// and `self.capabilities` says the reader is a terminal.
+    /// Without `self.terminal`, like with `Promptor::new()`, which can't tell whether the reader is stdin,
+    /// this function turns off echo on stdin if it is a terminal, which does no harm if the reader is something else.
+    /// Otherwise, e.g. when reading from a pipe or a `&[u8]`, this function reads the line as usual.
+    /// Unlike `rget_line()`, this function keeps leading and trailing spaces,
+    /// and only strips the line ending.
+    /// The secret comes back as a `SecretString`, which wipes its memory when dropped.
+    /// The buffer it is read into is wiped as well whenever it grows, and if anything fails along the way.
+    ///
+    /// Returns `PromptError` if:
+    /// * `write!()` or `self.writer.flush()` fails
+    /// * turning echo off or reading the line fails
+    /// * `self.reader` has reached end-of-input
+    ///
+    /// # Arguments
+    /// * `msg` – a message to display to the user.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use promptor::Promptor;
+    ///
+    /// let mut promptor = Promptor::stdio();
+    ///
+    /// let token = promptor.password("API token: ").unwrap();
+    ///
+    /// println!("Read a token of {} bytes.", token.expose_secret().len());
+    /// ```
+    pub fn password(&mut self, msg: &str) -> Result<SecretString, PromptError> {
+        let text = self.theme.prompt(msg, self.color());
+        write!(&mut self.writer, "{}", text)?;
+
+        // Force output to stdout before reading from stdin
+        self.writer.flush()?;
+
+        let line = {
+            let terminal = match self.terminal {
+                Some(terminal) if self.capabilities.input_is_terminal => Some(terminal),
+                Some(_) => None,
+                None => Terminal::stdin(),
+            };
+            let _echo_off = match terminal {
+                Some(terminal) => Some(terminal.hide_input()?),
+                None => None,
+            };
+
+            read_secret_line(&mut self.reader)?
+        };
+
+        line.ok_or(PromptError::EofError)
+    }
+
+    /// Same as `password()`, but asks the user to type the secret again with `confirm_msg`,
+    /// and starts over if the two don't match.
+    ///
+    /// # Arguments
+    /// * `msg` – a message to display to the user.
+    /// * `confirm_msg` – a message asking the user to type the secret again.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use promptor::Promptor;
+    ///
+    /// let mut promptor = Promptor::stdio();
+    ///
+    /// let password = promptor
+    ///     .password_confirmed("New password: ", "Retype new password: ")
+    ///     .unwrap();
+    /// ```
+    pub fn password_confirmed(
+        &mut self,
+        msg: &str,
+        confirm_msg: &str,
+    ) -> Result<SecretString, PromptError> {
+        loop {
+            let first = self.password(msg)?;
+            let second = self.password(confirm_msg)?;
+
+            if first.expose_secret() == second.expose_secret() {
+                break Ok(first);
+            }
+
+            // The entries are secret, so `{input}` stays empty.
+            let reason = "The entries don't match. Please try again.";
+            self.show_retry(None, "", reason, reason)?;
+        }
+    }
+}
+
+/// Reads a line from `reader`, without its line ending, or `None` at end-of-input.
+/// Unlike `BufRead::read_line()`, which leaves the old buffer behind unwiped when it grows,
+/// this wipes every buffer it is done with.
+fn read_secret_line<R>(reader: &mut R) -> io::Result<Option<SecretString>>
+where
+    R: BufRead,
+{
+    let mut line = Zeroizing::new(Vec::with_capacity(INITIAL_CAPACITY));
+
+    loop {
+        let available = match reader.fill_buf() {
+            Ok(available) => available,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+
+        if available.is_empty() {
+            break;
+        }
+
+        let (used, done) = match available.iter().position(|&byte| byte == b'\n') {
+            Some(newline) => (newline + 1, true),
+            None => (available.len(), false),
+        };
+
+        push(&mut line, &available[..used]);
+        reader.consume(used);
+
+        if done {
+            break;
+        }
+    }
+
+    if line.is_empty() {
+        return Ok(None);
+    }
+
+    let len = line
+        .iter()
+        .rposition(|&byte| byte != b'\r' && byte != b'\n')
+        .map_or(0, |last| last + 1);
+    // Truncating keeps the capacity, so the line ending is still wiped on drop.
+    line.truncate(len);
+
+    // Taking the bytes hands their buffer over to the `SecretString` without copying it.
+    match String::from_utf8(std::mem::take(&mut *line)) {
+        Ok(secret) => Ok(Some(SecretString::new(secret))),
+        Err(err) => {
+            drop(Zeroizing::new(err.into_bytes()));
+
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stream did not contain valid UTF-8",
+            ))
+        }
+    }
+}
+
+/// Appends `bytes` to `line`, moving it to a bigger buffer if it is full,
+/// and wiping the old buffer before it is freed.
+fn push(line: &mut Zeroizing<Vec<u8>>, bytes: &[u8]) {
+    if line.capacity() - line.len() < bytes.len() {
+        let mut grown = Vec::with_capacity((line.len() + bytes.len()).max(line.capacity() * 2));
+        grown.extend_from_slice(line);
+
+        // Replacing the old buffer drops it, which wipes it.
+        *line = Zeroizing::new(grown);
+    }
+
+    line.extend_from_slice(bytes);
+}
@@ -0,0 +1,254 @@
+//! # Prompt
+//!
+//! A builder for a single prompt, for when the positional arguments of
+//! `Promptor::prompt_with()` and friends aren't enough.
+//! Start one with `Promptor::ask()`, set the options you need, and finish it with `run()`.
+
+use std::io::{BufRead, Write};
+
+use super::completer::Completer;
+use super::history::History;
+use super::line_editor::Helpers;
+use super::retry::{rejection, RetryPolicy};
+use super::validator::Validator;
+use super::{PromptError, Promptor, SafeParsable};
+
+/// # Prompt
+///
+/// Describes a prompt for a value of type `T`, which `run()` asks through a `Promptor`.
+///
+/// # Example
+/// ```no_run
+/// use promptor::Promptor;
+/// use promptor::validator::range;
+///
+/// let stdio = std::io::stdin();
+/// let input = stdio.lock();
+/// let output = std::io::stdout();
+///
+/// let mut promptor = Promptor::new(input, output);
+///
+/// let port = promptor
+///     .ask::<u32>("Port")
+///     .default(8080)
+///     .validate(range(1..=65535))
+///     .retries(3)
+///     .run();
+/// ```
+pub struct Prompt<'p, T, R, W> {
+    promptor: &'p mut Promptor<R, W>,
+    msg: String,
+    default: Option<(T, String)>,
+    validators: Vec<Box<dyn Validator<T> + 'p>>,
+    policy: RetryPolicy,
+    help: Option<String>,
+    error_message: Option<String>,
+    trim: bool,
+    history: Option<String>,
+    completer: Option<Box<dyn Completer + 'p>>,
+}
+
+impl<'p, T, R, W> Prompt<'p, T, R, W>
+where
+    T: SafeParsable,
+    R: BufRead,
+    W: Write,
+{
+    pub(crate) fn new(promptor: &'p mut Promptor<R, W>, msg: &str) -> Self {
+        Prompt {
+            promptor,
+            msg: msg.to_owned(),
+            default: None,
+            validators: Vec::new(),
+            policy: RetryPolicy::default(),
+            help: None,
+            error_message: None,
+            trim: false,
+            history: None,
+            completer: None,
+        }
+    }
+
+    /// Offers `default` to the user, showing it after the message, like `Port [8080]: `,
+    /// and returning it if they enter an empty line.
+    /// With the line editor, it is also shown as a hint that the user can accept with Right and then edit.
+    /// The default is not validated.
+    pub fn default(mut self, default: T) -> Self
+    where
+        T: std::fmt::Display,
+    {
+        let shown = default.to_string();
+        self.default = Some((default, shown));
+        self
+    }
+
+    /// Checks the user's input with `validator`.
+    /// Calling this more than once checks the input with every validator, in order.
+    pub fn validate<V>(mut self, validator: V) -> Self
+    where
+        V: Validator<T> + 'p,
+    {
+        self.validators.push(Box::new(validator));
+        self
+    }
+
+    /// Lets the user try again `retries` times after their first attempt,
+    /// then gives up with `PromptError::TooManyAttempts`.
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.policy.max_attempts = Some(retries.saturating_add(1));
+        self
+    }
+
+    /// Replaces the retry policy, for when you also need a delay between attempts.
+    pub fn policy(mut self, policy: RetryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Shows `help` on its own line before asking for the first time.
+    pub fn help(mut self, help: &str) -> Self {
+        self.help = Some(help.to_owned());
+        self
+    }
+
+    /// Shows `error_message` instead of the reason the input was rejected when asking the user to try again,
+    /// and instead of `Promptor::retry_message`, with the same `{input}` and `{reason}` placeholders.
+    pub fn error_message(mut self, error_message: &str) -> Self {
+        self.error_message = Some(error_message.to_owned());
+        self
+    }
+
+    /// Whether to strip leading whitespace from the user's input as well as trailing whitespace,
+    /// which is always stripped. Off by default.
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Keeps the answers to this prompt in the history named `key`, in `Promptor::history`,
+    /// so that the line editor can recall them, in this run and later ones.
+    /// Only answers that pass validation are kept. Does nothing if `Promptor::history` isn't set.
+    /// History is only a convenience, so a history that can't be read starts out empty,
+    /// and one that can't be saved is left as it was, without failing the prompt.
+    pub fn history(mut self, key: &str) -> Self {
+        self.history = Some(key.to_owned());
+        self
+    }
+
+    /// Lets the user complete their input with Tab, using `completer`,
+    /// when the prompt is read with the line editor.
+    pub fn completer<C>(mut self, completer: C) -> Self
+    where
+        C: Completer + 'p,
+    {
+        self.completer = Some(Box::new(completer));
+        self
+    }
+
+    /// Asks the user until they enter a valid value, or run out of attempts.
+    /// The message is followed by the default, if there is one, and `": "`, styled with `Promptor::theme`.
+    /// Returns `PromptError` if:
+    /// * `rget_line()` fails, including when the reader has reached end-of-input
+    /// * `writeln!()` fails while showing the help or asking the user to try again
+    /// * the user runs out of attempts, in which case the error is `PromptError::TooManyAttempts`
+    pub fn run(self) -> Result<T, PromptError> {
+        let theme = &self.promptor.theme;
+        let color = self.promptor.color();
+
+        let text = match &self.default {
+            Some((_, shown)) => format!(
+                "{} {}: ",
+                theme.prompt(&self.msg, color),
+                theme.default_value(&format!("[{}]", shown), color)
+            ),
+            None => format!("{}: ", theme.prompt(&self.msg, color)),
+        };
+
+        self.run_as(&text)
+    }
+
+    /// Same as `run()`, but shows `text` exactly as given, which is expected to be styled already.
+    pub(crate) fn run_as(mut self, text: &str) -> Result<T, PromptError> {
+        if let Some(help) = &self.help {
+            writeln!(&mut self.promptor.writer, "{}", help)?;
+        }
+
+        let (mut default, shown) = match self.default.take() {
+            Some((default, shown)) => (Some(default), Some(shown)),
+            None => (None, None),
+        };
+        let mut history = match (&self.history, &self.promptor.history) {
+            (Some(key), Some(store)) => Some(
+                store
+                    .load(key)
+                    .unwrap_or_else(|_| History::new(store.max_entries)),
+            ),
+            _ => None,
+        };
+        let mut attempts: u32 = 0;
+
+        loop {
+            attempts += 1;
+
+            let helpers = Helpers {
+                history: history.as_ref(),
+                completer: self.completer.as_deref(),
+                default: shown.as_deref(),
+                ..Helpers::default()
+            };
+            let line = self.promptor.read_line_as(text, helpers)?;
+            let line = if self.trim { line.trim_start() } else { &line };
+
+            if line.is_empty() {
+                if let (Some(default), Some(shown)) = (default.take(), &shown) {
+                    self.promptor.echo_answer(&self.msg, shown)?;
+                    break Ok(default);
+                }
+            }
+
+            let error = match self.promptor.rread::<T>(line) {
+                Ok(val) => match self.check(&val) {
+                    Ok(()) => {
+                        if let Some(history) = &mut history {
+                            self.remember(history, line);
+                        }
+                        self.promptor.echo_answer(&self.msg, line)?;
+                        break Ok(val);
+                    }
+                    Err(reason) => PromptError::ValidationError(reason),
+                },
+                Err(e @ PromptError::ReadError { .. }) => e,
+                Err(e) => break Err(e),
+            };
+
+            let (reason, default) = rejection(&error);
+
+            if !self.policy.allows_retry(attempts) {
+                break Err(PromptError::TooManyAttempts {
+                    attempts,
+                    last: Box::new(error),
+                });
+            }
+
+            self.promptor
+                .show_retry(self.error_message.as_deref(), line, &reason, &default)?;
+
+            std::thread::sleep(self.policy.delay_after(attempts));
+        }
+    }
+
+    /// Adds `line` to `history`, and saves it if it can.
+    fn remember(&self, history: &mut History, line: &str) {
+        if let (Some(key), Some(store)) = (&self.history, &self.promptor.history) {
+            history.add(line);
+            // The user has already answered, so not being able to save their answer shouldn't lose it.
+            let _ = store.save(key, history);
+        }
+    }
+
+    fn check(&self, val: &T) -> Result<(), String> {
+        self.validators
+            .iter()
+            .try_for_each(|validator| validator.validate(val))
+    }
+}
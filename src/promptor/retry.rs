@@ -0,0 +1,146 @@
+//! # Retry
+//!
+//! Describes how many times a prompt asks the user again after invalid input,
+//! how long it waits between attempts, and what it tells the user in between.
+
+use std::io::{self, BufRead, Write};
+use std::time::Duration;
+
+use super::{PromptError, Promptor};
+
+/// What prompts tell the user when a validator that only returns a `bool` rejects their input.
+pub(crate) const INVALID_INPUT: &str = "Invalid input! Please try again.";
+
+/// # RetryPolicy
+///
+/// Limits how many attempts the user gets, and optionally waits between attempts,
+/// multiplying the wait by `backoff` after every failed attempt.
+/// The default policy retries forever without waiting, like `prompt()` does.
+///
+/// # Example
+/// Give the user three attempts, waiting one second after the first failure
+/// and two seconds after the second:
+/// ```
+/// use std::time::Duration;
+/// use promptor::promptor::retry::RetryPolicy;
+///
+/// let policy = RetryPolicy {
+///     delay: Duration::from_secs(1),
+///     backoff: 2,
+///     ..RetryPolicy::attempts(3)
+/// };
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The most attempts the user gets, or `None` to keep asking forever.
+    pub max_attempts: Option<u32>,
+
+    /// How long to wait after the first failed attempt.
+    pub delay: Duration,
+
+    /// How much to multiply `delay` by after each further failed attempt.
+    pub backoff: u32,
+}
+
+impl RetryPolicy {
+    /// A policy that gives the user at most `max_attempts` attempts, without waiting between them.
+    pub fn attempts(max_attempts: u32) -> Self {
+        RetryPolicy {
+            max_attempts: Some(max_attempts),
+            ..RetryPolicy::default()
+        }
+    }
+
+    /// Whether the user may try again after failing `attempts` times.
+    pub(crate) fn allows_retry(&self, attempts: u32) -> bool {
+        match self.max_attempts {
+            Some(max) => attempts < max,
+            None => true,
+        }
+    }
+
+    /// How long to wait after the user has failed `attempts` times.
+    pub(crate) fn delay_after(&self, attempts: u32) -> Duration {
+        let factor = self.backoff.saturating_pow(attempts.saturating_sub(1));
+
+        self.delay.saturating_mul(factor)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: None,
+            delay: Duration::from_secs(0),
+            backoff: 1,
+        }
+    }
+}
+
+impl<R, W> Promptor<R, W>
+where
+    R: BufRead,
+    W: Write,
+{
+    /// Tells the user that `input` was rejected because of `reason`, using `template` if there is one,
+    /// or else `self.retry_message`, or else `default`.
+    /// Styles it with `self.theme`, and writes it to `self.error_writer` if it is set, or else to `self.writer`.
+    pub(crate) fn show_retry(
+        &mut self,
+        template: Option<&str>,
+        input: &str,
+        reason: &str,
+        default: &str,
+    ) -> io::Result<()> {
+        let msg = match template.or(self.retry_message.as_deref()) {
+            Some(template) => fill(template, input, reason),
+            None => default.to_owned(),
+        };
+        let msg = self.theme.error(&msg, self.color());
+
+        match &mut self.error_writer {
+            Some(error_writer) => {
+                writeln!(error_writer, "{}", msg)?;
+                error_writer.flush()
+            }
+            None => writeln!(&mut self.writer, "{}", msg),
+        }
+    }
+}
+
+/// The reason to give the user for `error`, and the message to show them if there is no retry message.
+pub(crate) fn rejection(error: &PromptError) -> (String, String) {
+    match error {
+        PromptError::ReadError { source, .. } => (
+            source.to_string(),
+            format!("Invalid input: {}. Please try again.", source),
+        ),
+        PromptError::ValidationError(reason) => (reason.clone(), reason.clone()),
+        error => (error.to_string(), INVALID_INPUT.to_owned()),
+    }
+}
+
+/// Replaces `{input}` and `{reason}` in `template`, without looking for placeholders in what they are replaced with.
+fn fill(template: &str, input: &str, reason: &str) -> String {
+    let mut filled = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(after) = rest.strip_prefix("{input}") {
+            filled.push_str(input);
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("{reason}") {
+            filled.push_str(reason);
+            rest = after;
+        } else {
+            filled.push('{');
+            rest = &rest[1..];
+        }
+    }
+
+    filled.push_str(rest);
+    filled
+}
@@ -0,0 +1,55 @@
+//! # Secret
+//!
+//! A string that wipes its memory when dropped, for the secrets that `password()` returns.
+
+use std::fmt;
+
+use zeroize::Zeroize;
+
+/// # SecretString
+///
+/// Holds a secret, like a password or an API token, and overwrites it with zeros when dropped.
+/// Its `Debug` output never shows the secret; call `expose_secret()` to read it.
+///
+/// # Example
+/// ```
+/// use promptor::promptor::secret::SecretString;
+///
+/// let token = SecretString::new("hunter2".to_owned());
+///
+/// assert_eq!("hunter2", token.expose_secret());
+/// assert_eq!("SecretString(\"***\")", format!("{:?}", token));
+/// ```
+#[derive(Clone, Default)]
+pub struct SecretString(pub(crate) String);
+
+impl SecretString {
+    /// Wraps `secret`, taking over its buffer without copying it.
+    pub fn new(secret: String) -> Self {
+        SecretString(secret)
+    }
+
+    /// Returns the secret itself.
+    /// Avoid copying it into a plain `String`, which won't be wiped.
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        SecretString::new(secret)
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SecretString").field(&"***").finish()
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
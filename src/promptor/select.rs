@@ -0,0 +1,333 @@
+//! # Select
+//!
+//! Numbered menus, where the user picks an option by its number or by the start of its name,
+//! and numbered checklists, where the user picks several options with lists and ranges like `1,3,5-7`.
+
+use std::fmt::Display;
+use std::io::{BufRead, Write};
+
+use super::completer::choices;
+use super::line_editor::Helpers;
+use super::{PromptError, Promptor};
+
+/// # MultiSelectOptions
+///
+/// Limits how many options the user may pick in `Promptor::multi_select_with()`,
+/// and says which options are picked if the user enters an empty line.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MultiSelectOptions {
+    /// The fewest options the user has to pick.
+    pub min: usize,
+
+    /// The most options the user may pick, if there is a limit.
+    pub max: Option<usize>,
+
+    /// The indices of the options that are checked to begin with.
+    pub checked: Vec<usize>,
+}
+
+impl<R, W> Promptor<R, W>
+where
+    R: BufRead,
+    W: Write,
+{
+    /// Shows `items` as a numbered list, then asks the user to pick one,
+    /// either by its number or by a prefix that only one item starts with, ignoring case.
+    /// An answer that matches an item exactly picks that item even if it is a prefix of others.
+    /// If the answer doesn't pick exactly one item, this function asks the user again.
+    /// With the line editor, Tab completes the names of the items.
+    /// Returns the index of the chosen item in `items`, or `PromptError` if:
+    /// * `rget_line()` fails, including when `self.reader` has reached end-of-input
+    /// * `writeln!()` fails while showing the list or asking the user to try again
+    /// * `items` is empty, in which case the error is `PromptError::NoChoices`
+    ///
+    /// # Arguments
+    /// * `msg` – a message to display to the user after the list.
+    /// * `items` – the options to choose from.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use promptor::Promptor;
+    ///
+    /// let stdio = std::io::stdin();
+    /// let input = stdio.lock();
+    /// let output = std::io::stdout();
+    ///
+    /// let mut promptor = Promptor::new(input, output);
+    ///
+    /// let shells = ["bash", "fish", "zsh"];
+    /// let index = promptor.select("Which shell do you use? ", &shells).unwrap();
+    ///
+    /// println!("Configuring {}.", shells[index]);
+    /// ```
+    pub fn select<S>(&mut self, msg: &str, items: &[S]) -> Result<usize, PromptError>
+    where
+        S: Display,
+    {
+        if items.is_empty() {
+            return Err(PromptError::NoChoices);
+        }
+
+        let items: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+
+        for (index, item) in items.iter().enumerate() {
+            writeln!(&mut self.writer, "  {}) {}", index + 1, item)?;
+        }
+
+        let completer = choices(&items);
+        let helpers = Helpers {
+            completer: Some(&completer),
+            ..Helpers::default()
+        };
+
+        loop {
+            let line = self.rget_line_with(msg, helpers)?;
+
+            match choose(&items, &line) {
+                Ok(index) => {
+                    self.echo_answer(msg, &items[index])?;
+                    break Ok(index);
+                }
+                Err(reason) => self.show_retry(None, &line, &reason, &reason)?,
+            }
+        }
+    }
+
+    /// Shows `items` as a numbered checklist, then asks the user to pick any number of them,
+    /// with a comma-separated list of numbers and ranges like `1,3,5-7`, or `all` or `none`.
+    /// If the answer isn't valid, this function asks the user again.
+    /// Returns the indices of the chosen items in `items`, in ascending order, or `PromptError` if:
+    /// * `rget_line()` fails, including when `self.reader` has reached end-of-input
+    /// * `writeln!()` fails while showing the list or asking the user to try again
+    ///
+    /// # Arguments
+    /// * `msg` – a message to display to the user after the list.
+    /// * `items` – the options to choose from.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use promptor::Promptor;
+    ///
+    /// let stdio = std::io::stdin();
+    /// let input = stdio.lock();
+    /// let output = std::io::stdout();
+    ///
+    /// let mut promptor = Promptor::new(input, output);
+    ///
+    /// let platforms = ["linux", "macos", "windows", "wasm"];
+    /// let chosen = promptor.multi_select("Build for which platforms? ", &platforms).unwrap();
+    ///
+    /// for index in chosen {
+    ///     println!("Building for {}.", platforms[index]);
+    /// }
+    /// ```
+    pub fn multi_select<S>(&mut self, msg: &str, items: &[S]) -> Result<Vec<usize>, PromptError>
+    where
+        S: Display,
+    {
+        self.multi_select_with(msg, items, &MultiSelectOptions::default())
+    }
+
+    /// Same as `multi_select()`, but limits how many items the user may pick,
+    /// and checks some items to begin with, which the user keeps by entering an empty line.
+    /// Returns `PromptError::NoChoices`, without asking anything, if no choice could meet the limits,
+    /// because `options.min` is more than the number of items or more than `options.max`.
+    ///
+    /// # Arguments
+    /// * `msg` – a message to display to the user after the list.
+    /// * `items` – the options to choose from.
+    /// * `options` – the limits and the items checked to begin with.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use promptor::Promptor;
+    /// use promptor::promptor::select::MultiSelectOptions;
+    ///
+    /// let stdio = std::io::stdin();
+    /// let input = stdio.lock();
+    /// let output = std::io::stdout();
+    ///
+    /// let mut promptor = Promptor::new(input, output);
+    ///
+    /// let features = ["tls", "compression", "metrics"];
+    /// let options = MultiSelectOptions {
+    ///     min: 1,
+    ///     checked: vec![0],
+    ///     ..MultiSelectOptions::default()
+    /// };
+    /// let chosen = promptor.multi_select_with("Enable which features? ", &features, &options);
+    /// ```
+    pub fn multi_select_with<S>(
+        &mut self,
+        msg: &str,
+        items: &[S],
+        options: &MultiSelectOptions,
+    ) -> Result<Vec<usize>, PromptError>
+    where
+        S: Display,
+    {
+        if options.min > items.len() || matches!(options.max, Some(max) if options.min > max) {
+            return Err(PromptError::NoChoices);
+        }
+
+        for (index, item) in items.iter().enumerate() {
+            let mark = if options.checked.contains(&index) {
+                &self.theme.checked
+            } else {
+                &self.theme.unchecked
+            };
+            writeln!(&mut self.writer, "  {} {}) {}", mark, index + 1, item)?;
+        }
+
+        loop {
+            let line = self.rget_line(msg)?;
+
+            let chosen = if line.trim().is_empty() {
+                let mut checked: Vec<usize> = options
+                    .checked
+                    .iter()
+                    .copied()
+                    .filter(|&index| index < items.len())
+                    .collect();
+                checked.sort_unstable();
+                checked.dedup();
+                Ok(checked)
+            } else {
+                parse_selection(&line, items.len())
+            };
+
+            match chosen.and_then(|chosen| check_count(chosen, options)) {
+                Ok(chosen) => break Ok(chosen),
+                Err(reason) => self.show_retry(None, &line, &reason, &reason)?,
+            }
+        }
+    }
+}
+
+/// Reads a selection like `1,3,5-7`, `all` or `none` from a list of `len` items,
+/// returning the 0-based indices it picks in ascending order,
+/// or a message for the user if it can't be read.
+pub(crate) fn parse_selection(answer: &str, len: usize) -> Result<Vec<usize>, String> {
+    let answer = answer.trim();
+
+    if answer.eq_ignore_ascii_case("all") {
+        return Ok((0..len).collect());
+    }
+
+    if answer.eq_ignore_ascii_case("none") {
+        return Ok(Vec::new());
+    }
+
+    let number = |part: &str| -> Result<usize, String> {
+        match part.trim().parse::<usize>() {
+            Ok(number) if (1..=len).contains(&number) => Ok(number - 1),
+            Ok(number) => Err(format!("There is no option {}.", number)),
+            Err(_) => Err(format!(
+                "\"{}\" is not a number or a range like 2-4.",
+                part.trim()
+            )),
+        }
+    };
+
+    let mut chosen = Vec::new();
+
+    for part in answer.split(',').filter(|part| !part.trim().is_empty()) {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (number(start)?, number(end)?);
+
+                if start > end {
+                    return Err(format!(
+                        "The range {} is backwards; try {}-{}.",
+                        part.trim(),
+                        end + 1,
+                        start + 1
+                    ));
+                }
+
+                chosen.extend(start..=end);
+            }
+            None => chosen.push(number(part)?),
+        }
+    }
+
+    chosen.sort_unstable();
+    chosen.dedup();
+
+    Ok(chosen)
+}
+
+fn check_count(chosen: Vec<usize>, options: &MultiSelectOptions) -> Result<Vec<usize>, String> {
+    if chosen.len() < options.min {
+        return Err(format!(
+            "Please pick at least {}.",
+            options_count(options.min)
+        ));
+    }
+
+    match options.max {
+        Some(max) if chosen.len() > max => {
+            Err(format!("Please pick at most {}.", options_count(max)))
+        }
+        _ => Ok(chosen),
+    }
+}
+
+fn options_count(count: usize) -> String {
+    if count == 1 {
+        "1 option".to_owned()
+    } else {
+        format!("{} options", count)
+    }
+}
+
+/// Picks the item that `answer` refers to, either by its 1-based number or by its name,
+/// returning a message for the user if `answer` doesn't pick exactly one item.
+pub(crate) fn choose(items: &[String], answer: &str) -> Result<usize, String> {
+    let answer = answer.trim();
+
+    if let Ok(number) = answer.parse::<usize>() {
+        return if (1..=items.len()).contains(&number) {
+            Ok(number - 1)
+        } else {
+            Err(format!("Please enter a number from 1 to {}.", items.len()))
+        };
+    }
+
+    let query = answer.to_lowercase();
+
+    if query.is_empty() {
+        return Err(format!(
+            "Please enter a number from 1 to {} or the start of an option.",
+            items.len()
+        ));
+    }
+
+    if let Some(index) = items.iter().position(|item| item.to_lowercase() == query) {
+        return Ok(index);
+    }
+
+    let matches: Vec<usize> = items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| item.to_lowercase().starts_with(&query))
+        .map(|(index, _)| index)
+        .collect();
+
+    match matches.as_slice() {
+        [index] => Ok(*index),
+        [] => Err(format!(
+            "Please enter a number from 1 to {} or the start of an option.",
+            items.len()
+        )),
+        _ => Err(format!(
+            "\"{}\" could be any of: {}.",
+            answer,
+            matches
+                .iter()
+                .map(|&index| items[index].as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
@@ -0,0 +1,216 @@
+//! # Term
+//!
+//! Talks to the terminal behind a `Promptor`'s reader, for the prompts that need more
+//! than line-based input, like turning off echo for passwords or reading single key presses,
+//! and finds out whether its writer is a terminal too, and how big it is.
+//! Everything here is a no-op when the reader isn't a terminal.
+
+use std::io;
+
+#[cfg(unix)]
+use std::os::unix::io::RawFd;
+
+/// # Terminal
+///
+/// A handle to the terminal that a `Promptor`'s reader reads from,
+/// and to the one its writer writes to, if that is a terminal as well.
+/// `Promptor::stdio()` sets one up automatically if stdin is a terminal;
+/// for other readers, `Promptor::terminal` is `None` and prompts fall back to plain line-based input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Terminal {
+    #[cfg(unix)]
+    fd: RawFd,
+    #[cfg(unix)]
+    output: Option<RawFd>,
+}
+
+impl Terminal {
+    /// Returns the terminal attached to stdin, with stdout as its output if stdout is a terminal,
+    /// or `None` if stdin isn't a terminal.
+    pub fn stdio() -> Option<Terminal> {
+        #[cfg(unix)]
+        {
+            Terminal::stdin().map(|terminal| terminal.with_output(libc::STDOUT_FILENO))
+        }
+
+        #[cfg(not(unix))]
+        {
+            None
+        }
+    }
+
+    /// Returns the terminal attached to stdin, or `None` if stdin isn't a terminal.
+    pub fn stdin() -> Option<Terminal> {
+        #[cfg(unix)]
+        {
+            Terminal::from_fd(libc::STDIN_FILENO)
+        }
+
+        #[cfg(not(unix))]
+        {
+            None
+        }
+    }
+
+    /// Returns the terminal behind the file descriptor `fd`, or `None` if `fd` isn't a terminal.
+    #[cfg(unix)]
+    pub fn from_fd(fd: RawFd) -> Option<Terminal> {
+        if is_terminal(fd) {
+            Some(Terminal { fd, output: None })
+        } else {
+            None
+        }
+    }
+
+    /// Says that the `Promptor`'s writer writes to the file descriptor `fd`,
+    /// which prompts that draw on the screen, like `interactive_select()`, need to be a terminal.
+    /// If `fd` isn't a terminal, the writer is treated as a plain stream.
+    #[cfg(unix)]
+    pub fn with_output(self, fd: RawFd) -> Terminal {
+        Terminal {
+            output: Some(fd).filter(|&fd| is_terminal(fd)),
+            ..self
+        }
+    }
+
+    /// Whether the `Promptor`'s writer is a terminal, as set with `with_output()`.
+    pub fn output_is_terminal(&self) -> bool {
+        #[cfg(unix)]
+        {
+            self.output.is_some()
+        }
+
+        #[cfg(not(unix))]
+        {
+            false
+        }
+    }
+
+    /// The size of the output terminal, as columns and rows,
+    /// or `None` if the writer isn't a terminal or the size is unknown.
+    #[cfg(unix)]
+    pub fn size(&self) -> Option<(usize, usize)> {
+        // SAFETY: winsize is plain old data, which the ioctl fills in if it succeeds.
+        let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+
+        if unsafe { libc::ioctl(self.output?, libc::TIOCGWINSZ, &mut size) } != 0 {
+            return None;
+        }
+
+        if size.ws_col == 0 || size.ws_row == 0 {
+            return None;
+        }
+
+        Some((usize::from(size.ws_col), usize::from(size.ws_row)))
+    }
+
+    #[cfg(not(unix))]
+    pub fn size(&self) -> Option<(usize, usize)> {
+        None
+    }
+
+    /// Stops the terminal from echoing what the user types, except for the final newline,
+    /// until the returned guard is dropped.
+    #[cfg(unix)]
+    pub(crate) fn hide_input(&self) -> io::Result<ModeGuard> {
+        self.change_mode(|termios| {
+            termios.c_lflag &= !libc::ECHO;
+            termios.c_lflag |= libc::ECHONL;
+        })
+    }
+
+    #[cfg(not(unix))]
+    pub(crate) fn hide_input(&self) -> io::Result<ModeGuard> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+
+    /// Puts the terminal in raw mode, where every key press is sent straight away
+    /// without being echoed, until the returned guard is dropped.
+    /// Ctrl-C doesn't raise SIGINT in raw mode, so that the guard always gets to restore the terminal;
+    /// the prompts that use raw mode report it as an `Interrupted` error instead.
+    #[cfg(unix)]
+    pub(crate) fn raw_mode(&self) -> io::Result<ModeGuard> {
+        self.change_mode(|termios| {
+            termios.c_iflag &=
+                !(libc::BRKINT | libc::ICRNL | libc::INPCK | libc::ISTRIP | libc::IXON);
+            termios.c_lflag &= !(libc::ECHO | libc::ICANON | libc::IEXTEN | libc::ISIG);
+            termios.c_cflag |= libc::CS8;
+            termios.c_cc[libc::VMIN] = 1;
+            termios.c_cc[libc::VTIME] = 0;
+        })
+    }
+
+    #[cfg(not(unix))]
+    pub(crate) fn raw_mode(&self) -> io::Result<ModeGuard> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+
+    #[cfg(unix)]
+    fn change_mode<F>(&self, change: F) -> io::Result<ModeGuard>
+    where
+        F: FnOnce(&mut libc::termios),
+    {
+        // SAFETY: termios is plain old data, and tcgetattr fills it in before we read it.
+        let mut original: libc::termios = unsafe { std::mem::zeroed() };
+
+        if unsafe { libc::tcgetattr(self.fd, &mut original) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mut changed = original;
+        change(&mut changed);
+
+        if unsafe { libc::tcsetattr(self.fd, libc::TCSANOW, &changed) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(ModeGuard {
+            fd: self.fd,
+            original,
+        })
+    }
+}
+
+/// Puts `terminal` in raw mode, if there is one, until the returned guard is dropped.
+/// Without one, the reader is expected to send key presses anyway, like a script in a test.
+pub(crate) fn raw_mode(terminal: Option<&Terminal>) -> io::Result<Option<ModeGuard>> {
+    terminal.map(Terminal::raw_mode).transpose()
+}
+
+/// Whether stdout is a terminal, whether or not stdin is one.
+pub(crate) fn stdout_is_terminal() -> bool {
+    #[cfg(unix)]
+    {
+        is_terminal(libc::STDOUT_FILENO)
+    }
+
+    #[cfg(not(unix))]
+    {
+        false
+    }
+}
+
+#[cfg(unix)]
+fn is_terminal(fd: RawFd) -> bool {
+    // SAFETY: isatty only inspects the descriptor, and fails cleanly if it is invalid.
+    unsafe { libc::isatty(fd) == 1 }
+}
+
+/// Restores the terminal's original mode when dropped,
+/// so that an early return or a panic doesn't leave echo turned off.
+pub(crate) struct ModeGuard {
+    #[cfg(unix)]
+    fd: RawFd,
+    #[cfg(unix)]
+    original: libc::termios,
+}
+
+impl Drop for ModeGuard {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        // SAFETY: `original` came from tcgetattr on the same descriptor.
+        unsafe {
+            libc::tcsetattr(self.fd, libc::TCSANOW, &self.original);
+        }
+    }
+}
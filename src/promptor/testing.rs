@@ -0,0 +1,283 @@
+//! # Testing
+//!
+//! Helps you test code that asks the user questions, by answering them from a script
+//! and checking that the code asks what you expect, in order.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Read, Write};
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+
+use super::capabilities::Capabilities;
+use super::Promptor;
+
+/// # ScriptedPromptor
+///
+/// A `Promptor` that answers prompts from a script of questions and answers,
+/// for testing code that asks the user questions.
+/// Every time the code reads a line, the last line it wrote before, with the surrounding whitespace trimmed,
+/// has to be the next question in the script, and the answer is read as the user's line.
+/// It panics with what it expected and what was asked if the code asks something else,
+/// asks more questions than the script has, or, once it is dropped or finished, fewer.
+///
+/// It keeps a transcript of everything written, with the answers after their questions,
+/// as they would show on a terminal, for snapshot tests.
+/// It always uses plain line-based input and output, without colours, like a `Promptor` without a terminal.
+/// It dereferences to the `Promptor`; use `promptor()` to pass it to functions that take one.
+///
+/// # Example
+/// ```
+/// use std::io::{BufRead, Write};
+///
+/// use promptor::Promptor;
+/// use promptor::testing::ScriptedPromptor;
+///
+/// fn configure<R: BufRead, W: Write>(promptor: &mut Promptor<R, W>) -> (String, u32) {
+///     let host = promptor.rget_line("Host: ").unwrap();
+///     let port = promptor.ask::<u32>("Port").default(8080).run().unwrap();
+///
+///     (host, port)
+/// }
+///
+/// let mut scripted = ScriptedPromptor::new(vec![
+///     ("Host:", "localhost"),
+///     ("Port [8080]:", "x"),
+///     ("Port [8080]:", ""),
+/// ]);
+///
+/// assert_eq!(("localhost".to_owned(), 8080), configure(scripted.promptor()));
+/// assert_eq!(
+///     "Host: localhost\n\
+///      Port [8080]: x\n\
+///      Invalid input: invalid digit found in string. Please try again.\n\
+///      Port [8080]: \n",
+///     scripted.finish()
+/// );
+/// ```
+pub struct ScriptedPromptor {
+    promptor: Promptor<ScriptReader, ScriptWriter>,
+    script: Rc<RefCell<Script>>,
+    finished: bool,
+}
+
+impl ScriptedPromptor {
+    /// Creates a `ScriptedPromptor` that expects the questions in `script`, in order,
+    /// and answers each with the answer next to it.
+    ///
+    /// # Arguments
+    /// * `script` – pairs of a question, like `"Port [8080]:"`, and the line to answer it with, like `"80"`.
+    pub fn new<I, Q, A>(script: I) -> Self
+    where
+        I: IntoIterator<Item = (Q, A)>,
+        Q: Into<String>,
+        A: Into<String>,
+    {
+        let script = Rc::new(RefCell::new(Script {
+            expected: script
+                .into_iter()
+                .map(|(question, answer)| (question.into(), answer.into()))
+                .collect(),
+            asked: 0,
+            pending: Vec::new(),
+            transcript: Vec::new(),
+        }));
+
+        let reader = ScriptReader {
+            script: Rc::clone(&script),
+            line: Vec::new(),
+            position: 0,
+        };
+        let writer = ScriptWriter {
+            script: Rc::clone(&script),
+        };
+
+        let mut promptor = Promptor::new(reader, writer);
+        promptor.capabilities = Capabilities::default();
+
+        ScriptedPromptor {
+            promptor,
+            script,
+            finished: false,
+        }
+    }
+
+    /// The `Promptor` that answers from the script.
+    pub fn promptor(&mut self) -> &mut Promptor<ScriptReader, ScriptWriter> {
+        &mut self.promptor
+    }
+
+    /// Everything written so far, with the answers after their questions.
+    pub fn transcript(&self) -> String {
+        String::from_utf8_lossy(&self.script.borrow().transcript).into_owned()
+    }
+
+    /// Checks that every question in the script was asked, and returns the transcript.
+    ///
+    /// # Panics
+    /// Panics with the questions that were never asked, if there are any.
+    pub fn finish(mut self) -> String {
+        self.finished = true;
+        self.script.borrow().check_done();
+
+        self.transcript()
+    }
+}
+
+impl Deref for ScriptedPromptor {
+    type Target = Promptor<ScriptReader, ScriptWriter>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.promptor
+    }
+}
+
+impl DerefMut for ScriptedPromptor {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.promptor
+    }
+}
+
+impl Drop for ScriptedPromptor {
+    fn drop(&mut self) {
+        // Don't panic again if the test is already failing.
+        if !self.finished && !std::thread::panicking() {
+            self.script.borrow().check_done();
+        }
+    }
+}
+
+/// The questions still to ask, and what has been written.
+struct Script {
+    expected: VecDeque<(String, String)>,
+    /// How many questions have been asked so far.
+    asked: usize,
+    /// What has been written since the last answer, which ends with the next question.
+    pending: Vec<u8>,
+    transcript: Vec<u8>,
+}
+
+impl Script {
+    /// Checks that `question` is the next question in the script, and returns its answer.
+    fn answer(&mut self) -> String {
+        let pending = String::from_utf8_lossy(&self.pending);
+        let question = pending
+            .lines()
+            .rev()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or_default()
+            .to_owned();
+
+        self.asked += 1;
+
+        let (expected, answer) = match self.expected.pop_front() {
+            Some(next) => next,
+            None => panic!(
+                "question {} wasn't in the script, which only has {}:\n+ asked:    {}\n\ntranscript so far:\n{}",
+                self.asked,
+                self.asked - 1,
+                question,
+                String::from_utf8_lossy(&self.transcript)
+            ),
+        };
+
+        if expected.trim() != question {
+            panic!(
+                "question {} didn't match the script:\n- expected: {}\n+ asked:    {}\n\ntranscript so far:\n{}",
+                self.asked,
+                expected.trim(),
+                question,
+                String::from_utf8_lossy(&self.transcript)
+            );
+        }
+
+        self.pending.clear();
+        self.transcript.extend_from_slice(answer.as_bytes());
+        self.transcript.push(b'\n');
+
+        answer
+    }
+
+    /// Panics if any questions in the script haven't been asked.
+    fn check_done(&self) {
+        if self.expected.is_empty() {
+            return;
+        }
+
+        let missing: Vec<String> = self
+            .expected
+            .iter()
+            .map(|(question, _)| format!("- {}", question.trim()))
+            .collect();
+
+        panic!(
+            "only {} of the {} questions in the script were asked; these never were:\n{}\n\ntranscript:\n{}",
+            self.asked,
+            self.asked + self.expected.len(),
+            missing.join("\n"),
+            String::from_utf8_lossy(&self.transcript)
+        );
+    }
+}
+
+/// # ScriptReader
+///
+/// The reader of a `ScriptedPromptor`, which reads the answers in its script.
+pub struct ScriptReader {
+    script: Rc<RefCell<Script>>,
+    /// The answer being read, with its line ending.
+    line: Vec<u8>,
+    position: usize,
+}
+
+impl Read for ScriptReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let len = available.len().min(buf.len());
+
+        buf[..len].copy_from_slice(&available[..len]);
+        self.consume(len);
+
+        Ok(len)
+    }
+}
+
+impl BufRead for ScriptReader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.position == self.line.len() {
+            let answer = self.script.borrow_mut().answer();
+
+            self.line = format!("{}\n", answer).into_bytes();
+            self.position = 0;
+        }
+
+        Ok(&self.line[self.position..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.position = (self.position + amount).min(self.line.len());
+    }
+}
+
+/// # ScriptWriter
+///
+/// The writer of a `ScriptedPromptor`, which keeps what it is given for the transcript.
+pub struct ScriptWriter {
+    script: Rc<RefCell<Script>>,
+}
+
+impl Write for ScriptWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut script = self.script.borrow_mut();
+
+        script.pending.extend_from_slice(buf);
+        script.transcript.extend_from_slice(buf);
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
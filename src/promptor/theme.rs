@@ -0,0 +1,285 @@
+//! # Theme
+//!
+//! Decides how prompts look: the symbol before them, their colours,
+//! how defaults, errors and answers are shown, and the markers in menus and checklists.
+//! Colours are only used when `Promptor::capabilities` allows them.
+
+use std::io::{self, BufRead, Write};
+
+use super::Promptor;
+
+/// # Color
+///
+/// One of the eight standard terminal colours, which every terminal supports
+/// and which users can adjust in their terminal's settings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Color {
+    fn code(self) -> u8 {
+        match self {
+            Color::Black => 30,
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::White => 37,
+        }
+    }
+}
+
+/// # Style
+///
+/// How to show a piece of text: its colour, and whether it is bold or dimmed.
+/// The default style shows text as it is.
+///
+/// # Example
+/// ```
+/// use promptor::promptor::theme::{Color, Style};
+///
+/// let style = Style {
+///     color: Some(Color::Cyan),
+///     bold: true,
+///     ..Style::default()
+/// };
+///
+/// assert_eq!("\x1b[1;36mPort\x1b[0m", style.paint("Port"));
+/// assert_eq!("Port", Style::default().paint("Port"));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Style {
+    /// The colour of the text, or `None` for the terminal's own.
+    pub color: Option<Color>,
+
+    /// Whether the text is bold.
+    pub bold: bool,
+
+    /// Whether the text is dimmed.
+    pub dim: bool,
+}
+
+impl Style {
+    /// Wraps `text` in the ANSI escape sequences for this style.
+    pub fn paint(&self, text: &str) -> String {
+        let mut codes = Vec::new();
+
+        if self.bold {
+            codes.push(1.to_string());
+        }
+        if self.dim {
+            codes.push(2.to_string());
+        }
+        if let Some(color) = self.color {
+            codes.push(color.code().to_string());
+        }
+
+        if codes.is_empty() || text.is_empty() {
+            return text.to_owned();
+        }
+
+        format!("\x1b[{}m{}\x1b[0m", codes.join(";"), text)
+    }
+}
+
+/// # Theme
+///
+/// Decides how `Promptor` shows prompts, defaults, errors, answers and menus.
+/// The default theme shows everything as plain text, exactly as given;
+/// `Theme::colorful()` is a ready-made theme in the style of other modern command-line tools.
+/// Set it as `Promptor::theme`.
+///
+/// # Example
+/// ```no_run
+/// use promptor::Promptor;
+/// use promptor::promptor::theme::{Color, Theme};
+///
+/// let mut promptor = Promptor::stdio();
+///
+/// let mut theme = Theme::colorful();
+/// theme.prompt.color = Some(Color::Magenta);
+/// promptor.theme = theme;
+///
+/// // Shows "? Port [8080]: ", then "✔ Port · 8080" once the user has answered.
+/// let port = promptor.ask::<u32>("Port").default(8080).run();
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Theme {
+    /// Shown before every prompt, like `? `.
+    pub prefix: String,
+
+    /// The style of `prefix`.
+    pub prefix_style: Style,
+
+    /// The style of the prompt's message.
+    pub prompt: Style,
+
+    /// The style of defaults and hints, like `[8080]` or `[Y/n]`.
+    pub default: Style,
+
+    /// Shown before messages that ask the user to try again, like `✘ `.
+    pub error_prefix: String,
+
+    /// The style of messages that ask the user to try again.
+    pub error: Style,
+
+    /// If set, replaces a prompt the user has answered with this symbol, the message and the answer,
+    /// like `✔ Port · 8080`, on a terminal.
+    pub success_prefix: Option<String>,
+
+    /// The style of `success_prefix`.
+    pub success: Style,
+
+    /// The style of the answer after a prompt has been answered.
+    pub answer: Style,
+
+    /// Shown before the highlighted item in interactive menus.
+    pub active_marker: String,
+
+    /// Shown before the other items in interactive menus, as wide as `active_marker`.
+    pub inactive_marker: String,
+
+    /// Shows that an item is checked in `multi_select()`.
+    pub checked: String,
+
+    /// Shows that an item isn't checked in `multi_select()`.
+    pub unchecked: String,
+}
+
+impl Theme {
+    /// A theme with a `?` before prompts, bold messages, dimmed defaults, red errors,
+    /// green checkmarks for answered prompts, and a `❯` for the highlighted item in menus.
+    pub fn colorful() -> Self {
+        Theme {
+            prefix: "? ".to_owned(),
+            prefix_style: Style {
+                color: Some(Color::Cyan),
+                bold: true,
+                ..Style::default()
+            },
+            prompt: Style {
+                bold: true,
+                ..Style::default()
+            },
+            default: Style {
+                dim: true,
+                ..Style::default()
+            },
+            error_prefix: "✘ ".to_owned(),
+            error: Style {
+                color: Some(Color::Red),
+                ..Style::default()
+            },
+            success_prefix: Some("✔".to_owned()),
+            success: Style {
+                color: Some(Color::Green),
+                bold: true,
+                ..Style::default()
+            },
+            answer: Style {
+                color: Some(Color::Cyan),
+                ..Style::default()
+            },
+            active_marker: "❯ ".to_owned(),
+            inactive_marker: "  ".to_owned(),
+            checked: "[x]".to_owned(),
+            unchecked: "[ ]".to_owned(),
+        }
+    }
+
+    /// The prefix and `msg`, styled if `color` is set.
+    pub(crate) fn prompt(&self, msg: &str, color: bool) -> String {
+        format!(
+            "{}{}",
+            paint(&self.prefix_style, &self.prefix, color),
+            paint(&self.prompt, msg, color)
+        )
+    }
+
+    /// A default or hint, styled if `color` is set.
+    pub(crate) fn default_value(&self, text: &str, color: bool) -> String {
+        paint(&self.default, text, color)
+    }
+
+    /// A message asking the user to try again, with the error prefix, styled if `color` is set.
+    pub(crate) fn error(&self, msg: &str, color: bool) -> String {
+        paint(&self.error, &format!("{}{}", self.error_prefix, msg), color)
+    }
+
+    /// The line that replaces an answered prompt, if the theme has one.
+    /// Trailing spaces and a colon are taken off the message, so `"Port: "` becomes `"Port"`.
+    pub(crate) fn success_line(&self, msg: &str, answer: &str, color: bool) -> Option<String> {
+        let symbol = self.success_prefix.as_ref()?;
+        let msg = msg.trim_end().trim_end_matches(':');
+
+        Some(format!(
+            "{} {} · {}",
+            paint(&self.success, symbol, color),
+            paint(&self.prompt, msg, color),
+            paint(&self.answer, answer, color)
+        ))
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            prefix: String::new(),
+            prefix_style: Style::default(),
+            prompt: Style::default(),
+            default: Style::default(),
+            error_prefix: String::new(),
+            error: Style::default(),
+            success_prefix: None,
+            success: Style::default(),
+            answer: Style::default(),
+            active_marker: "> ".to_owned(),
+            inactive_marker: "  ".to_owned(),
+            checked: "[x]".to_owned(),
+            unchecked: "[ ]".to_owned(),
+        }
+    }
+}
+
+/// `text` in `style` if `color` is set, or as it is otherwise.
+pub(crate) fn paint(style: &Style, text: &str, color: bool) -> String {
+    if color {
+        style.paint(text)
+    } else {
+        text.to_owned()
+    }
+}
+
+impl<R, W> Promptor<R, W>
+where
+    R: BufRead,
+    W: Write,
+{
+    /// Whether to use the theme's colours, as `self.capabilities` says.
+    pub(crate) fn color(&self) -> bool {
+        self.capabilities.color()
+    }
+
+    /// Replaces the line above, where the user just answered `msg`, with the theme's success line,
+    /// if the theme has one and the prompt is `interactive()`, so that the user's Enter moved to a new line.
+    pub(crate) fn echo_answer(&mut self, msg: &str, answer: &str) -> io::Result<()> {
+        if !self.capabilities.interactive() {
+            return Ok(());
+        }
+
+        match self.theme.success_line(msg, answer, self.color()) {
+            Some(line) => writeln!(&mut self.writer, "\x1b[1A\r\x1b[K{}", line),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,394 @@
+//! # Validator
+//!
+//! Validators decide whether a value the user entered is acceptable,
+//! and explain to the user why it is not.
+//! You can pass any of them, or any closure of the form `Fn(&T) -> Result<(), String>`,
+//! to `Promptor::prompt_with()`.
+//!
+//! # Example
+//! ```
+//! use promptor::validator::{range, Validator};
+//!
+//! let percentage = range(0..=100);
+//!
+//! assert!(percentage.validate(&50).is_ok());
+//! assert_eq!(
+//!     Err("Input must be between 0 and 100.".to_owned()),
+//!     percentage.validate(&101)
+//! );
+//! ```
+
+use std::fmt::Display;
+use std::ops::{Bound, RangeBounds};
+
+/// # Validator
+///
+/// Checks a value of type `T`, returning `Ok(())` if it is acceptable,
+/// or `Err` with a message for the user if it is not.
+///
+/// Validators can be combined with `and()`, `or()` and `not()`,
+/// and their message can be replaced with `message()`.
+pub trait Validator<T: ?Sized> {
+    /// Checks `value`, returning a message for the user if it is not acceptable.
+    fn validate(&self, value: &T) -> Result<(), String>;
+
+    /// Accepts a value only if both `self` and `other` accept it.
+    /// Fails with the message of whichever validator rejected the value first.
+    fn and<V>(self, other: V) -> And<Self, V>
+    where
+        Self: Sized,
+        V: Validator<T>,
+    {
+        And(self, other)
+    }
+
+    /// Accepts a value if either `self` or `other` accepts it.
+    /// If both reject it, fails with both messages joined with "or".
+    fn or<V>(self, other: V) -> Or<Self, V>
+    where
+        Self: Sized,
+        V: Validator<T>,
+    {
+        Or(self, other)
+    }
+
+    /// Accepts a value only if `self` rejects it.
+    /// Because there is no way to turn `self`'s message around,
+    /// you have to supply the message to show instead.
+    fn not<S>(self, msg: S) -> Not<Self>
+    where
+        Self: Sized,
+        S: Into<String>,
+    {
+        Not(self, msg.into())
+    }
+
+    /// Replaces the message of `self` with `msg`.
+    fn message<S>(self, msg: S) -> Message<Self>
+    where
+        Self: Sized,
+        S: Into<String>,
+    {
+        Message(self, msg.into())
+    }
+}
+
+impl<T, F> Validator<T> for F
+where
+    T: ?Sized,
+    F: Fn(&T) -> Result<(), String>,
+{
+    fn validate(&self, value: &T) -> Result<(), String> {
+        self(value)
+    }
+}
+
+/// # And
+///
+/// The validator returned by `Validator::and()`.
+#[derive(Clone, Debug)]
+pub struct And<A, B>(A, B);
+
+impl<T, A, B> Validator<T> for And<A, B>
+where
+    T: ?Sized,
+    A: Validator<T>,
+    B: Validator<T>,
+{
+    fn validate(&self, value: &T) -> Result<(), String> {
+        self.0.validate(value).and_then(|()| self.1.validate(value))
+    }
+}
+
+/// # Or
+///
+/// The validator returned by `Validator::or()`.
+#[derive(Clone, Debug)]
+pub struct Or<A, B>(A, B);
+
+impl<T, A, B> Validator<T> for Or<A, B>
+where
+    T: ?Sized,
+    A: Validator<T>,
+    B: Validator<T>,
+{
+    fn validate(&self, value: &T) -> Result<(), String> {
+        let first = match self.0.validate(value) {
+            Ok(()) => return Ok(()),
+            Err(reason) => reason,
+        };
+
+        self.1
+            .validate(value)
+            .map_err(|second| join_alternatives(&first, &second))
+    }
+}
+
+/// # Not
+///
+/// The validator returned by `Validator::not()`.
+#[derive(Clone, Debug)]
+pub struct Not<A>(A, String);
+
+impl<T, A> Validator<T> for Not<A>
+where
+    T: ?Sized,
+    A: Validator<T>,
+{
+    fn validate(&self, value: &T) -> Result<(), String> {
+        match self.0.validate(value) {
+            Ok(()) => Err(self.1.clone()),
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+/// # Message
+///
+/// The validator returned by `Validator::message()`.
+#[derive(Clone, Debug)]
+pub struct Message<A>(A, String);
+
+impl<T, A> Validator<T> for Message<A>
+where
+    T: ?Sized,
+    A: Validator<T>,
+{
+    fn validate(&self, value: &T) -> Result<(), String> {
+        self.0.validate(value).map_err(|_| self.1.clone())
+    }
+}
+
+/// # InRange
+///
+/// The validator returned by `range()`.
+#[derive(Clone, Debug)]
+pub struct InRange<R>(R);
+
+/// Accepts values inside `bounds`, which can be any kind of range, e.g. `1..=100` or `0.0..`.
+///
+/// # Example
+/// ```
+/// use promptor::validator::{range, Validator};
+///
+/// assert!(range(1..=100).validate(&100).is_ok());
+/// assert_eq!(Err("Input must be at least 1.".to_owned()), range(1..).validate(&0));
+/// ```
+pub fn range<R>(bounds: R) -> InRange<R> {
+    InRange(bounds)
+}
+
+impl<T, R> Validator<T> for InRange<R>
+where
+    T: PartialOrd + Display,
+    R: RangeBounds<T>,
+{
+    fn validate(&self, value: &T) -> Result<(), String> {
+        if self.0.contains(value) {
+            return Ok(());
+        }
+
+        let reason = match (self.0.start_bound(), self.0.end_bound()) {
+            (Bound::Included(start), Bound::Included(end)) => {
+                format!("between {} and {}", start, end)
+            }
+            (Bound::Included(start), Bound::Excluded(end)) => {
+                format!("at least {} and less than {}", start, end)
+            }
+            (Bound::Excluded(start), Bound::Included(end)) => {
+                format!("greater than {} and at most {}", start, end)
+            }
+            (Bound::Excluded(start), Bound::Excluded(end)) => {
+                format!("greater than {} and less than {}", start, end)
+            }
+            (Bound::Included(start), Bound::Unbounded) => format!("at least {}", start),
+            (Bound::Excluded(start), Bound::Unbounded) => format!("greater than {}", start),
+            (Bound::Unbounded, Bound::Included(end)) => format!("at most {}", end),
+            (Bound::Unbounded, Bound::Excluded(end)) => format!("less than {}", end),
+            (Bound::Unbounded, Bound::Unbounded) => unreachable!("`..` contains every value"),
+        };
+
+        Err(format!("Input must be {}.", reason))
+    }
+}
+
+/// # MinLen
+///
+/// The validator returned by `min_len()`.
+#[derive(Clone, Copy, Debug)]
+pub struct MinLen(usize);
+
+/// Accepts strings that are at least `len` characters long.
+pub fn min_len(len: usize) -> MinLen {
+    MinLen(len)
+}
+
+impl<T> Validator<T> for MinLen
+where
+    T: AsRef<str> + ?Sized,
+{
+    fn validate(&self, value: &T) -> Result<(), String> {
+        if value.as_ref().chars().count() >= self.0 {
+            Ok(())
+        } else {
+            Err(format!("Input must be at least {}.", characters(self.0)))
+        }
+    }
+}
+
+/// # MaxLen
+///
+/// The validator returned by `max_len()`.
+#[derive(Clone, Copy, Debug)]
+pub struct MaxLen(usize);
+
+/// Accepts strings that are at most `len` characters long.
+pub fn max_len(len: usize) -> MaxLen {
+    MaxLen(len)
+}
+
+impl<T> Validator<T> for MaxLen
+where
+    T: AsRef<str> + ?Sized,
+{
+    fn validate(&self, value: &T) -> Result<(), String> {
+        if value.as_ref().chars().count() <= self.0 {
+            Ok(())
+        } else {
+            Err(format!("Input must be at most {}.", characters(self.0)))
+        }
+    }
+}
+
+/// # NonEmpty
+///
+/// The validator returned by `non_empty()`.
+#[derive(Clone, Copy, Debug)]
+pub struct NonEmpty;
+
+/// Accepts strings that contain something other than whitespace.
+pub fn non_empty() -> NonEmpty {
+    NonEmpty
+}
+
+impl<T> Validator<T> for NonEmpty
+where
+    T: AsRef<str> + ?Sized,
+{
+    fn validate(&self, value: &T) -> Result<(), String> {
+        if value.as_ref().trim().is_empty() {
+            Err("Input must not be empty.".to_owned())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// # OneOf
+///
+/// The validator returned by `one_of()`.
+#[derive(Clone, Debug)]
+pub struct OneOf<T>(Vec<T>);
+
+/// Accepts only the values in `choices`.
+///
+/// # Example
+/// ```
+/// use promptor::validator::{one_of, Validator};
+///
+/// let odd_digit = one_of(vec![1, 3, 5, 7, 9]);
+///
+/// assert!(odd_digit.validate(&3).is_ok());
+/// assert_eq!(
+///     Err("Input must be one of: 1, 3, 5, 7, 9.".to_owned()),
+///     odd_digit.validate(&4)
+/// );
+/// ```
+pub fn one_of<T, I>(choices: I) -> OneOf<T>
+where
+    I: IntoIterator<Item = T>,
+{
+    OneOf(choices.into_iter().collect())
+}
+
+impl<T> Validator<T> for OneOf<T>
+where
+    T: PartialEq + Display,
+{
+    fn validate(&self, value: &T) -> Result<(), String> {
+        if self.0.contains(value) {
+            return Ok(());
+        }
+
+        let choices = self
+            .0
+            .iter()
+            .map(|choice| choice.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        Err(format!("Input must be one of: {}.", choices))
+    }
+}
+
+/// # Pattern
+///
+/// The validator returned by `pattern()`.
+#[cfg(feature = "regex")]
+#[derive(Clone, Debug)]
+pub struct Pattern(regex::Regex);
+
+/// Accepts strings that `regex` matches.
+/// Anchor the expression with `^` and `$` if the whole string has to match.
+///
+/// Requires the `regex` feature.
+///
+/// # Example
+/// ```
+/// use promptor::validator::{pattern, Validator};
+/// use regex::Regex;
+///
+/// let hex_colour = pattern(Regex::new(r"^#[0-9a-fA-F]{6}$").unwrap());
+///
+/// assert!(hex_colour.validate("#fa7268").is_ok());
+/// assert!(hex_colour.validate("fa7268").is_err());
+/// ```
+#[cfg(feature = "regex")]
+pub fn pattern(regex: regex::Regex) -> Pattern {
+    Pattern(regex)
+}
+
+#[cfg(feature = "regex")]
+impl<T> Validator<T> for Pattern
+where
+    T: AsRef<str> + ?Sized,
+{
+    fn validate(&self, value: &T) -> Result<(), String> {
+        if self.0.is_match(value.as_ref()) {
+            Ok(())
+        } else {
+            Err(format!("Input must match the pattern {}.", self.0))
+        }
+    }
+}
+
+fn characters(len: usize) -> String {
+    if len == 1 {
+        "1 character long".to_owned()
+    } else {
+        format!("{} characters long", len)
+    }
+}
+
+/// Joins two messages into one sentence,
+/// e.g. "Input must be at most 10." and "Input must be at least 90."
+/// into "Input must be at most 10 or input must be at least 90."
+fn join_alternatives(first: &str, second: &str) -> String {
+    let mut second_chars = second.chars();
+    let second = match second_chars.next() {
+        Some(c) => c.to_lowercase().chain(second_chars).collect(),
+        None => String::new(),
+    };
+
+    format!("{} or {}", first.trim_end_matches('.'), second)
+}
@@ -5,6 +5,7 @@
 /// to the functions in the maybe module. The only difference
 /// is that I would be checking for certain errors rather than None.
 use std::str::FromStr;
+use crate::promptor::PromptError;
 use crate::Promptor;
 
 // From https://rust-lang-nursery.github.io/rust-cookbook/text/string_parsing.html
@@ -42,10 +43,7 @@ fn sanity_checks() {
     let input = b"";
     let mut output = Vec::new();
 
-    let mut promptor = Promptor {
-        reader: &input[..],
-        writer: &mut output,
-    };
+    let mut promptor = Promptor::new(&input[..], &mut output);
 
     // parse and from_str should always be equal for the same arguments.
     assert_eq!("32".parse::<i32>().unwrap(), i32::from_str("32").unwrap());
@@ -74,10 +72,7 @@ fn composite_type_checks() {
     let input = b"";
     let mut output = Vec::new();
 
-    let mut promptor = Promptor {
-        reader: &input[..],
-        writer: &mut output,
-    };
+    let mut promptor = Promptor::new(&input[..], &mut output);
 
     // Read should behave the same way as calling parse or calling from_str directly on the type.
     let call_through_trait = RGB::from_str(r"#fa7268").unwrap()
@@ -109,10 +104,7 @@ fn chaining_checks() {
     let input = b"";
     let mut output = Vec::new();
 
-    let mut promptor = Promptor {
-        reader: &input[..],
-        writer: &mut output,
-    };
+    let mut promptor = Promptor::new(&input[..], &mut output);
 
     let res = promptor.read::<i32>("32").map(|x| x * 2).unwrap_or_default();
 
@@ -148,10 +140,7 @@ fn stdio_good_input_check() {
     let input = b"32";
     let mut output = Vec::new();
 
-    let mut promptor = Promptor {
-        reader: &input[..],
-        writer: &mut output,
-    };
+    let mut promptor = Promptor::new(&input[..], &mut output);
 
     let res = promptor
         .input::<i32>("What's your favourite number? ")
@@ -169,10 +158,7 @@ fn stdio_bad_input_check() {
     let input = b"gdfg32";
     let mut output = Vec::new();
 
-    let mut promptor = Promptor {
-        reader: &input[..],
-        writer: &mut output,
-    };
+    let mut promptor = Promptor::new(&input[..], &mut output);
 
     let res = promptor.input::<i32>("What's your favourite number? ");
 
@@ -190,12 +176,9 @@ fn stdio_good_prompt_check() {
     let input = b"32";
     let mut output = Vec::new();
 
-    let mut promptor = Promptor {
-        reader: &input[..],
-        writer: &mut output,
-    };
+    let mut promptor = Promptor::new(&input[..], &mut output);
 
-    let res: i32 = promptor.prompt("Please enter a number between 1 and 50: ", |x| {
+    let res: i32 = promptor.prompt("Please enter a number between 1 and 50: ", |&x| {
         1 <= x && x <= 50
     });
 
@@ -213,12 +196,9 @@ fn stdio_bad_prompt_check() {
     let input = b"3ghhj2\n25";
     let mut output = Vec::new();
 
-    let mut promptor = Promptor {
-        reader: &input[..],
-        writer: &mut output,
-    };
+    let mut promptor = Promptor::new(&input[..], &mut output);
 
-    let res: i32 = promptor.prompt("Please enter a number between 1 and 50: ", |x| {
+    let res: i32 = promptor.prompt("Please enter a number between 1 and 50: ", |&x| {
         1 <= x && x <= 50
     });
 
@@ -227,3 +207,1664 @@ fn stdio_bad_prompt_check() {
     assert_eq!("Please enter a number between 1 and 50: Invalid input! Please try again.\nPlease enter a number between 1 and 50: ", output);
     assert_eq!(25, res);
 }
+
+/// In this test, I am checking that running out of input is reported as its own error
+/// rather than as an empty line.
+#[test]
+fn stdio_eof_check() {
+    let input = b"";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    assert!(promptor.get_line("What's your name? ").is_none());
+    assert!(matches!(
+        promptor.rget_line("What's your name? "),
+        Err(PromptError::EofError)
+    ));
+    assert!(matches!(
+        promptor.rinput::<i32>("What's your favourite number? "),
+        Err(PromptError::EofError)
+    ));
+}
+
+/// In this test, I am checking that `try_prompt` gives up at end-of-input
+/// instead of re-prompting forever.
+#[test]
+fn stdio_eof_prompt_check() {
+    let input = b"3ghhj2\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    let res = promptor.try_prompt::<i32, _>("Please enter a number between 1 and 50: ", |x| {
+        (1..=50).contains(x)
+    });
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!("Please enter a number between 1 and 50: Invalid input: invalid digit found in string. Please try again.\nPlease enter a number between 1 and 50: ", output);
+    assert!(matches!(res, Err(PromptError::EofError)));
+}
+
+/// Ditto, but for the infallible `prompt`, which should panic instead of hanging.
+#[test]
+#[should_panic(expected = "reached end of input before getting valid input")]
+fn stdio_eof_panic_check() {
+    let input = b"3ghhj2\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    let _: i32 = promptor.prompt("Please enter a number between 1 and 50: ", |x| {
+        (1..=50).contains(x)
+    });
+}
+
+/// In this test, I am checking that `rread` keeps both the text the user typed
+/// and the reason `from_str` gave for rejecting it.
+#[test]
+fn read_error_source_check() {
+    let input = b"";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    match promptor.rread::<u8>("300") {
+        Err(PromptError::ReadError { input, source }) => {
+            assert_eq!("300", input);
+            assert_eq!("number too large to fit in target type", source.to_string());
+            assert!(source.downcast_ref::<std::num::ParseIntError>().is_some());
+        }
+        _ => panic!("expected a ReadError"),
+    }
+}
+
+/// In this test, I am checking that `rprompt` shows the user why their input was rejected.
+#[test]
+fn stdio_rprompt_reason_check() {
+    let input = b"300\n25";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    let res: u8 = promptor.rprompt("Please enter a number between 1 and 50: ", |x| {
+        (1..=50).contains(x)
+    });
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!("Please enter a number between 1 and 50: Invalid input: number too large to fit in target type. Please try again.\nPlease enter a number between 1 and 50: ", output);
+    assert_eq!(25, res);
+}
+
+/// In this test, I am checking that `prompt_with` shows the validator's own message
+/// instead of the generic one.
+#[test]
+fn stdio_prompt_with_message_check() {
+    let input = b"0\n25";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    let res: i32 = promptor.prompt_with("Please enter a number between 1 and 50: ", |x: &i32| {
+        if (1..=50).contains(x) {
+            Ok(())
+        } else {
+            Err("The number must be between 1 and 50.".to_owned())
+        }
+    });
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!("Please enter a number between 1 and 50: The number must be between 1 and 50.\nPlease enter a number between 1 and 50: ", output);
+    assert_eq!(25, res);
+}
+
+/// In this test, I am checking that the ready-made validators accept and reject
+/// the values I expect, with messages that tell the user what went wrong.
+#[test]
+fn validator_checks() {
+    use crate::validator::{max_len, min_len, non_empty, one_of, range, Validator};
+
+    assert!(range(1..=100).validate(&1).is_ok());
+    assert!(range(1..=100).validate(&100).is_ok());
+    assert_eq!(
+        Err("Input must be between 1 and 100.".to_owned()),
+        range(1..=100).validate(&101)
+    );
+    assert_eq!(
+        Err("Input must be less than 1.5.".to_owned()),
+        range(..1.5).validate(&1.5)
+    );
+
+    assert!(min_len(2).validate("ab").is_ok());
+    assert_eq!(
+        Err("Input must be at least 3 characters long.".to_owned()),
+        min_len(3).validate("二é")
+    );
+    assert!(max_len(2).validate("二é").is_ok());
+    assert_eq!(
+        Err("Input must be at most 1 character long.".to_owned()),
+        max_len(1).validate("ab")
+    );
+
+    assert!(non_empty().validate("a").is_ok());
+    assert!(non_empty().validate("  ").is_err());
+
+    assert!(one_of(vec!["red", "green"]).validate(&"red").is_ok());
+    assert_eq!(
+        Err("Input must be one of: red, green.".to_owned()),
+        one_of(vec!["red", "green"]).validate(&"blue")
+    );
+}
+
+/// In this test, I am checking that the combinators compose validators
+/// and pick sensible messages.
+#[test]
+fn validator_combinator_checks() {
+    use crate::validator::{range, Validator};
+
+    let outer = range(..=10).or(range(90..));
+    assert!(outer.validate(&5).is_ok());
+    assert!(outer.validate(&95).is_ok());
+    assert_eq!(
+        Err("Input must be at most 10 or input must be at least 90.".to_owned()),
+        outer.validate(&50)
+    );
+
+    let even = |x: &i32| {
+        if x % 2 == 0 {
+            Ok(())
+        } else {
+            Err("Input must be even.".to_owned())
+        }
+    };
+    let small_even = range(0..10).and(even);
+    assert!(small_even.validate(&4).is_ok());
+    assert_eq!(Err("Input must be even.".to_owned()), small_even.validate(&5));
+    assert_eq!(
+        Err("Input must be at least 0 and less than 10.".to_owned()),
+        small_even.validate(&12)
+    );
+
+    let not_thirteen = range(13..=13).not("Input must not be 13.");
+    assert!(not_thirteen.validate(&12).is_ok());
+    assert_eq!(Err("Input must not be 13.".to_owned()), not_thirteen.validate(&13));
+
+    let polite = range(1..=5).message("Please pick a rating from 1 to 5.");
+    assert_eq!(
+        Err("Please pick a rating from 1 to 5.".to_owned()),
+        polite.validate(&0)
+    );
+}
+
+/// In this test, I am checking that `prompt_with` accepts a `Validator` and
+/// shows its message when it rejects the input.
+#[test]
+fn stdio_prompt_with_validator_check() {
+    use crate::validator::range;
+
+    let input = b"51\n25";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    let res: i32 = promptor.prompt_with("Please enter a number between 1 and 50: ", range(1..=50));
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!("Please enter a number between 1 and 50: Input must be between 1 and 50.\nPlease enter a number between 1 and 50: ", output);
+    assert_eq!(25, res);
+}
+
+/// In this test, I am checking that types that own heap data, like `String` and `PathBuf`,
+/// can be prompted for, and that their validators only need to borrow them.
+#[test]
+fn stdio_owned_prompt_check() {
+    use std::path::PathBuf;
+
+    let input = b"\nWillow\n/tmp/promptor.txt\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    let name: String = promptor.prompt("What's your name? ", |s: &String| !s.is_empty());
+    let path: PathBuf = promptor.prompt("Where should I save it? ", |p: &PathBuf| {
+        p.is_absolute()
+    });
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!("What's your name? Invalid input! Please try again.\nWhat's your name? Where should I save it? ", output);
+    assert_eq!("Willow", name);
+    assert_eq!(PathBuf::from("/tmp/promptor.txt"), path);
+}
+
+/// In this test, I am checking that a `RetryPolicy` stops asking after the allowed
+/// number of attempts and hands back the last reason the input was rejected.
+#[test]
+fn stdio_retry_policy_check() {
+    use crate::promptor::retry::RetryPolicy;
+    use crate::validator::range;
+
+    let input = b"0\n99\n25\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    let res = promptor.try_prompt_with_policy::<i32, _>(
+        "Please enter a number between 1 and 10: ",
+        range(1..=10),
+        &RetryPolicy::attempts(2),
+    );
+
+    // The last answer is still waiting to be read.
+    assert_eq!(Some(25), promptor.input::<i32>(""));
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!("Please enter a number between 1 and 10: Input must be between 1 and 10.\nPlease enter a number between 1 and 10: ", output);
+    match res {
+        Err(PromptError::TooManyAttempts { attempts, last }) => {
+            assert_eq!(2, attempts);
+            assert!(matches!(*last, PromptError::ValidationError(ref reason) if reason == "Input must be between 1 and 10."));
+        }
+        _ => panic!("expected TooManyAttempts"),
+    }
+}
+
+/// In this test, I am checking that the delay between attempts grows by the backoff factor.
+#[test]
+fn retry_policy_delay_check() {
+    use crate::promptor::retry::RetryPolicy;
+    use std::time::Duration;
+
+    let policy = RetryPolicy {
+        delay: Duration::from_millis(100),
+        backoff: 3,
+        ..RetryPolicy::attempts(4)
+    };
+
+    assert_eq!(Duration::from_millis(100), policy.delay_after(1));
+    assert_eq!(Duration::from_millis(300), policy.delay_after(2));
+    assert_eq!(Duration::from_millis(900), policy.delay_after(3));
+    assert!(policy.allows_retry(3));
+    assert!(!policy.allows_retry(4));
+    assert!(RetryPolicy::default().allows_retry(u32::MAX));
+}
+
+/// In this test, I am checking that `prompt_with_default` shows the default
+/// and returns it when the user just presses Enter.
+#[test]
+fn stdio_default_prompt_check() {
+    use crate::validator::range;
+
+    let input = b"\n0\n\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    let first: u16 = promptor.prompt_with_default("Port", 8080, range(1..));
+    let second: u16 = promptor.prompt_with_default("Port", 8080, range(1..));
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!("Port [8080]: Port [8080]: Input must be at least 1.\nPort [8080]: ", output);
+    assert_eq!(8080, first);
+    assert_eq!(8080, second);
+}
+
+/// In this test, I am checking that the `Prompt` builder puts its options together:
+/// help text, a default, several validators and a retry limit.
+#[test]
+fn stdio_builder_check() {
+    use crate::validator::{range, Validator};
+
+    let input = b"70000\n81\n8081\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    let res = promptor
+        .ask::<u32>("Port")
+        .help("The port the server listens on.")
+        .default(8080)
+        .validate(range(1..=65535))
+        .validate(range(1024..).message("Ports below 1024 need root."))
+        .retries(2)
+        .run();
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!("The port the server listens on.\nPort [8080]: Input must be between 1 and 65535.\nPort [8080]: Ports below 1024 need root.\nPort [8080]: ", output);
+    assert_eq!(8081, res.unwrap());
+}
+
+/// In this test, I am checking the builder's custom error message, trimming and retry limit.
+#[test]
+fn stdio_builder_error_message_check() {
+    let input = b"  abc\n  42  \n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    let res = promptor
+        .ask::<i32>("Answer")
+        .error_message("That's not it.")
+        .trim(true)
+        .retries(1)
+        .run();
+
+    assert_eq!(42, res.unwrap());
+
+    let res = promptor.ask::<i32>("Answer").retries(0).run();
+
+    assert!(matches!(res, Err(PromptError::EofError)));
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!("Answer: That's not it.\nAnswer: Answer: ", output);
+}
+
+/// In this test, I am checking that `confirm` accepts yes/no in any case,
+/// uses the default on an empty line and asks again otherwise.
+#[test]
+fn stdio_confirm_check() {
+    let input = b"YES\nmaybe\nn\n\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    assert!(promptor.confirm("Continue?", None).unwrap());
+    assert!(!promptor.confirm("Continue?", Some(true)).unwrap());
+    assert!(!promptor.confirm("Continue?", Some(false)).unwrap());
+    assert!(matches!(
+        promptor.confirm("Continue?", None),
+        Err(PromptError::EofError)
+    ));
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!("Continue? [y/n] Continue? [Y/n] Please answer yes or no.\nContinue? [Y/n] Continue? [y/N] Continue? [y/n] ", output);
+}
+
+/// In this test, I am checking that `confirm_with` accepts localized answers.
+#[test]
+fn stdio_confirm_localized_check() {
+    use crate::promptor::confirm::Answers;
+
+    let input = b"yes\nNein\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    let german = Answers {
+        yes: vec!["j".to_owned(), "ja".to_owned()],
+        no: vec!["n".to_owned(), "nein".to_owned()],
+        error_message: "Bitte mit ja oder nein antworten.".to_owned(),
+    };
+
+    assert!(!promptor.confirm_with("Fortfahren?", Some(true), &german).unwrap());
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!("Fortfahren? [J/n] Bitte mit ja oder nein antworten.\nFortfahren? [J/n] ", output);
+}
+
+/// In this test, I am checking that `select` shows a numbered list and accepts
+/// either a number or a unique prefix, asking again when the answer is out of range or ambiguous.
+#[test]
+fn stdio_select_check() {
+    let input = b"4\nb\nBlue\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    let colours = ["red", "blue", "blueberry"];
+    let res = promptor.select("Pick a colour: ", &colours).unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!("  1) red\n  2) blue\n  3) blueberry\nPick a colour: Please enter a number from 1 to 3.\nPick a colour: \"b\" could be any of: blue, blueberry.\nPick a colour: ", output);
+    assert_eq!(1, res);
+}
+
+/// In this test, I am checking that the menus return `NoChoices` for an empty list, without reading or writing anything.
+#[test]
+fn select_no_choices_check() {
+    use crate::promptor::capabilities::Capabilities;
+
+    let input = b"1\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+    let items: [&str; 0] = [];
+
+    assert!(matches!(promptor.select("Pick: ", &items), Err(PromptError::NoChoices)));
+    assert!(matches!(promptor.fuzzy_select("Pick: ", &items), Err(PromptError::NoChoices)));
+
+    promptor.capabilities = Capabilities {
+        input_is_terminal: true,
+        output_is_terminal: true,
+        ..Capabilities::default()
+    };
+    assert!(matches!(promptor.interactive_select("Pick: ", &items), Err(PromptError::NoChoices)));
+    assert!(matches!(promptor.fuzzy_select("Pick: ", &items), Err(PromptError::NoChoices)));
+
+    assert_eq!(2, promptor.reader.len());
+    assert!(output.is_empty());
+}
+
+/// In this test, I am checking how `choose` reads answers on its own.
+#[test]
+fn select_choose_check() {
+    use crate::promptor::select::choose;
+
+    let items = vec!["apple".to_owned(), "banana".to_owned(), "cherry".to_owned()];
+
+    assert_eq!(Ok(0), choose(&items, "1"));
+    assert_eq!(Ok(2), choose(&items, " 3 "));
+    assert_eq!(Ok(1), choose(&items, "BAN"));
+    assert!(choose(&items, "0").is_err());
+    assert!(choose(&items, "durian").is_err());
+    assert!(choose(&items, "").is_err());
+}
+
+/// In this test, I am checking that `parse_selection` understands lists, ranges, `all` and `none`.
+#[test]
+fn select_parse_selection_check() {
+    use crate::promptor::select::parse_selection;
+
+    assert_eq!(Ok(vec![0, 2, 4, 5, 6]), parse_selection("1,3,5-7", 8));
+    assert_eq!(Ok(vec![1, 2]), parse_selection(" 3, 2 ,3 ", 8));
+    assert_eq!(Ok(vec![0, 1, 2]), parse_selection("ALL", 3));
+    assert_eq!(Ok(vec![]), parse_selection("none", 3));
+    assert_eq!(
+        Err("There is no option 9.".to_owned()),
+        parse_selection("1,9", 8)
+    );
+    assert!(parse_selection("2-x", 8).is_err());
+    assert!(parse_selection("5-3", 8).is_err());
+}
+
+/// In this test, I am checking that `multi_select_with` marks the checked items,
+/// keeps them on an empty line, and enforces the limits.
+#[test]
+fn stdio_multi_select_check() {
+    use crate::promptor::select::MultiSelectOptions;
+
+    let input = b"all\nnone\n\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    let options = MultiSelectOptions {
+        min: 1,
+        max: Some(2),
+        checked: vec![2],
+    };
+    let res = promptor
+        .multi_select_with("Targets: ", &["linux", "macos", "windows"], &options)
+        .unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!("  [ ] 1) linux\n  [ ] 2) macos\n  [x] 3) windows\nTargets: Please pick at most 2 options.\nTargets: Please pick at least 1 option.\nTargets: ", output);
+    assert_eq!(vec![2], res);
+}
+
+/// In this test, I am checking that `multi_select_with` returns `NoChoices` for limits no answer could meet,
+/// without asking anything, and still asks when there are no items but no limits either.
+#[test]
+fn multi_select_no_choices_check() {
+    use crate::promptor::select::MultiSelectOptions;
+
+    let input = b"all\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    let too_many = MultiSelectOptions {
+        min: 3,
+        ..MultiSelectOptions::default()
+    };
+    let crossed = MultiSelectOptions {
+        min: 2,
+        max: Some(1),
+        ..MultiSelectOptions::default()
+    };
+    let empty: [&str; 0] = [];
+
+    for (items, options) in &[(&["a", "b"][..], &too_many), (&["a", "b"][..], &crossed), (&empty[..], &too_many)] {
+        let res = promptor.multi_select_with("Pick: ", items, options);
+        assert!(matches!(res, Err(PromptError::NoChoices)));
+    }
+
+    assert!(promptor.multi_select_with("Pick: ", &empty, &MultiSelectOptions::default()).is_ok());
+
+    assert!(promptor.reader.is_empty());
+    assert_eq!("Pick: ", String::from_utf8(output).unwrap());
+}
+
+/// In this test, I am checking that `password` falls back to reading a plain line
+/// when the reader isn't a terminal, keeping the spaces around the secret.
+#[test]
+fn stdio_password_check() {
+    let input = b" hunter2 \r\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    assert!(promptor.terminal.is_none());
+    assert_eq!(
+        " hunter2 ",
+        promptor.password("Password: ").unwrap().expose_secret()
+    );
+    assert!(matches!(
+        promptor.password("Password: "),
+        Err(PromptError::EofError)
+    ));
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!("Password: Password: ", output);
+
+    // A secret longer than the buffer starts out with, like a PEM key, and one that isn't UTF-8.
+    let long = "k".repeat(5000);
+    let input = [format!("{}\n", long).as_bytes(), b"\xff\n"].concat();
+
+    let mut promptor = Promptor::new(&input[..], Vec::new());
+
+    assert_eq!(long, promptor.password("Key: ").unwrap().expose_secret());
+    assert!(matches!(
+        promptor.password("Key: "),
+        Err(PromptError::StdinError(e)) if e.kind() == std::io::ErrorKind::InvalidData
+    ));
+}
+
+/// In this test, I am checking that `password_confirmed` starts over when the entries don't match.
+#[test]
+fn stdio_password_confirmed_check() {
+    let input = b"hunter2\nhunter3\nhunter2\nhunter2\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    let res = promptor
+        .password_confirmed("Password: ", "Again: ")
+        .unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!("Password: Again: The entries don't match. Please try again.\nPassword: Again: ", output);
+    assert_eq!("hunter2", res.expose_secret());
+}
+
+/// In this test, I am checking that a `SecretString` never shows its secret through `Debug`.
+#[test]
+fn secret_debug_check() {
+    use crate::promptor::secret::SecretString;
+
+    let input = b"hunter2\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    let secret = promptor.password("Password: ").unwrap();
+
+    assert_eq!("hunter2", secret.expose_secret());
+    assert!(!format!("{:?}", secret).contains("hunter2"));
+    assert_eq!(
+        "SecretString(\"***\")",
+        format!("{:?}", SecretString::from("swordfish".to_owned()))
+    );
+}
+
+/// In this test, I am checking that `get_lines` reads up to each kind of terminator,
+/// and that end-of-input always ends the text.
+#[test]
+fn stdio_get_lines_check() {
+    use crate::promptor::lines::Terminator;
+
+    let input = b"Line one\r\n  indented \n.\nfirst\n\nsecond\nlast";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    let sentinel = promptor
+        .get_lines("Text:\n", Terminator::Sentinel(".".to_owned()))
+        .unwrap();
+    let blank = promptor.get_lines("", Terminator::BlankLine).unwrap();
+    let eof = promptor.get_lines("", Terminator::Eof).unwrap();
+
+    assert_eq!("Line one\n  indented ", sentinel);
+    assert_eq!("first", blank);
+    assert_eq!("second\nlast", eof);
+    assert!(matches!(
+        promptor.get_lines("", Terminator::Eof),
+        Err(PromptError::EofError)
+    ));
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!("Text:\n", output);
+}
+
+/// In this test, I am checking that `get_lines_with` stops at the line and byte limits.
+#[test]
+fn stdio_get_lines_limit_check() {
+    use crate::promptor::lines::{LinesOptions, Terminator};
+
+    let input = b"a\nb\nc\n\nabcdef\n\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    let max_lines = LinesOptions {
+        max_lines: Some(2),
+        ..LinesOptions::new(Terminator::BlankLine)
+    };
+    match promptor.get_lines_with("", &max_lines) {
+        Err(PromptError::LimitError(limit)) => assert_eq!("2 lines", limit),
+        _ => panic!("expected a LimitError"),
+    }
+
+    // Skip what's left of the first text.
+    assert_eq!("", promptor.get_lines("", Terminator::BlankLine).unwrap());
+
+    let max_bytes = LinesOptions {
+        max_bytes: Some(5),
+        ..LinesOptions::new(Terminator::BlankLine)
+    };
+    match promptor.get_lines_with("", &max_bytes) {
+        Err(PromptError::LimitError(limit)) => assert_eq!("5 bytes", limit),
+        _ => panic!("expected a LimitError"),
+    }
+
+    // Text that just fits, with a Windows line ending.
+    let input = b"ab\r\ncd\n\n";
+    let mut promptor = Promptor::new(&input[..], Vec::new());
+    assert_eq!("ab\ncd", promptor.get_lines_with("", &max_bytes).unwrap());
+
+    // A huge line without a newline is cut off without being read in full.
+    let input = vec![b'x'; 10_000_000];
+    let mut promptor = Promptor::new(&input[..], Vec::new());
+
+    assert!(matches!(
+        promptor.get_lines_with("", &max_bytes),
+        Err(PromptError::LimitError(_))
+    ));
+    assert!(promptor.reader.len() > input.len() - 100);
+}
+
+/// In this test, I am checking that `edit_with` runs the editor on the template
+/// and strips the comments from what it saved.
+/// The "editor" is a shell script that checks the template, and that only the user can read it,
+/// and writes a message.
+#[cfg(unix)]
+#[test]
+fn edit_check() {
+    let script = std::env::temp_dir().join(format!("promptor-editor-{}.sh", std::process::id()));
+    std::fs::write(
+        &script,
+        "ls -l \"$1\" | grep -q '^-rw-------' || exit 4\ngrep -q '^# Template' \"$1\" || exit 3\nprintf '\\n# Comment\\nSubject  \\n\\nBody\\n\\n' > \"$1\"\n",
+    )
+    .unwrap();
+
+    let editor = format!("sh {}", script.display());
+
+    let input = b"";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    let res = promptor.edit_with(&editor, "\n# Template\n");
+    let failed = promptor.edit_with(&editor, "No template here.\n");
+
+    std::fs::remove_file(&script).unwrap();
+
+    assert_eq!("Subject\n\nBody", res.unwrap());
+    match failed {
+        Err(PromptError::EditorError { reason, .. }) => assert!(reason.contains('3')),
+        _ => panic!("expected an EditorError"),
+    }
+}
+
+/// In this test, I am checking that `edit` prefers `$VISUAL` to `$EDITOR`,
+/// and falls back to the default editor when they are unset or blank.
+#[test]
+fn edit_pick_editor_check() {
+    use crate::promptor::edit::pick_editor;
+
+    let set = |editor: &str| Some(editor.to_owned());
+
+    assert_eq!("code --wait", pick_editor(set("code --wait"), set("nano")));
+    assert_eq!("nano", pick_editor(None, set("nano")));
+    assert_eq!("nano", pick_editor(set(" "), set("nano")));
+    assert!(!pick_editor(None, None).is_empty());
+    assert_eq!(pick_editor(None, None), pick_editor(None, set("  ")));
+}
+
+/// In this test, I am checking how `strip_comments` cleans up the editor's output on its own.
+#[test]
+fn edit_strip_comments_check() {
+    use crate::promptor::edit::strip_comments;
+
+    assert_eq!("a\n  b", strip_comments("\n\n# x\na \t\n  b\n# y\n\n"));
+    assert_eq!("", strip_comments("# only comments\n"));
+    assert_eq!("not # a comment", strip_comments("not # a comment"));
+}
+
+/// In this test, I am checking that the line editor moves the cursor, inserts in the middle,
+/// and jumps to either end of the line.
+#[test]
+fn line_editor_cursor_check() {
+    use crate::promptor::line_editor::LineEditor;
+
+    // "ac", Left, "b", Home, ">", End (escape sequence), "!", Enter.
+    let input = b"ac\x1b[Db\x01>\x1b[F!\r";
+    let mut reader = &input[..];
+    let mut output = Vec::new();
+
+    let mut editor = LineEditor::new();
+
+    let res = editor.read_line(&mut reader, &mut output, "> ").unwrap();
+
+    assert_eq!(Some(">abc!".to_owned()), res);
+    // Inserting in the middle redraws the line and moves the cursor back over the "c".
+    assert!(String::from_utf8(output).unwrap().contains("\r> abc\x1b[K\x1b[1D"));
+}
+
+/// In this test, I am checking that Backspace removes whole characters, even multi-byte ones,
+/// and that Ctrl-W and Ctrl-U remove a word and everything before the cursor.
+#[test]
+fn line_editor_deletion_check() {
+    use crate::promptor::line_editor::LineEditor;
+
+    let input = "café二\x7f\x7f!\rone two  \x17three\r one two\x15x\r".as_bytes();
+    let mut reader = input;
+    let mut output = Vec::new();
+
+    let mut editor = LineEditor::new();
+
+    let first = editor.read_line(&mut reader, &mut output, "").unwrap();
+    let second = editor.read_line(&mut reader, &mut output, "").unwrap();
+    let third = editor.read_line(&mut reader, &mut output, "").unwrap();
+
+    assert_eq!(Some("caf!".to_owned()), first);
+    assert_eq!(Some("one three".to_owned()), second);
+    assert_eq!(Some("x".to_owned()), third);
+}
+
+/// In this test, I am checking that Up and Down recall earlier lines,
+/// and that Down past the newest line brings back what the user was typing.
+#[test]
+fn line_editor_history_check() {
+    use crate::promptor::line_editor::LineEditor;
+
+    let input = b"first\rsecond\rsecond\r\rdraft\x1b[A\x1b[A\x1b[A\x1b[B\r\x1b[A\x1b[B\x1b[B\r";
+    let mut reader = &input[..];
+    let mut output = Vec::new();
+
+    let mut editor = LineEditor::new();
+
+    for _ in 0..4 {
+        editor.read_line(&mut reader, &mut output, "").unwrap();
+    }
+
+    // Empty lines and repeats of the last line aren't remembered.
+    assert_eq!(&["first".to_owned(), "second".to_owned()], editor.history());
+
+    assert_eq!(
+        Some("second".to_owned()),
+        editor.read_line(&mut reader, &mut output, "").unwrap()
+    );
+    assert_eq!(
+        Some(String::new()),
+        editor.read_line(&mut reader, &mut output, "").unwrap()
+    );
+}
+
+/// In this test, I am checking that Ctrl-D on an empty line ends the input,
+/// and that Ctrl-C interrupts it.
+#[test]
+fn line_editor_eof_interrupt_check() {
+    use crate::promptor::line_editor::LineEditor;
+
+    let input = b"\x04abc\x03";
+    let mut reader = &input[..];
+    let mut output = Vec::new();
+
+    let mut editor = LineEditor::new();
+
+    assert_eq!(None, editor.read_line(&mut reader, &mut output, "").unwrap());
+    assert_eq!(
+        std::io::ErrorKind::Interrupted,
+        editor.read_line(&mut reader, &mut output, "").unwrap_err().kind()
+    );
+    assert_eq!(None, editor.read_line(&mut reader, &mut output, "").unwrap());
+}
+
+/// In this test, I am checking that Ctrl-C in the line editor stops `prompt()`, `rprompt()` and `prompt_with()`
+/// instead of asking the user again.
+#[test]
+fn line_editor_interrupt_check() {
+    use crate::promptor::capabilities::Capabilities;
+    use crate::promptor::line_editor::LineEditor;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    for which in 0..3 {
+        let input = b"\x03\x03\x035\r";
+        let mut output = Vec::new();
+
+        let mut promptor = Promptor::new(&input[..], &mut output);
+        promptor.line_editor = Some(LineEditor::new());
+        promptor.capabilities = Capabilities {
+            input_is_terminal: true,
+            output_is_terminal: true,
+            ..Capabilities::default()
+        };
+
+        let res = catch_unwind(AssertUnwindSafe(|| -> u32 {
+            match which {
+                0 => promptor.prompt("> ", |_| true),
+                1 => promptor.rprompt("> ", |_| true),
+                _ => promptor.prompt_with("> ", |_: &u32| Ok(())),
+            }
+        }));
+
+        let message = res.unwrap_err();
+        assert_eq!(
+            Some(&"failed to prompt for input: Failure reading line from stdin".to_owned()),
+            message.downcast_ref::<String>()
+        );
+        assert_eq!(4, promptor.reader.len());
+        assert!(!String::from_utf8(output).unwrap().contains("try again"));
+    }
+}
+
+/// In this test, I am checking that a `Promptor` without a terminal
+/// reads plain lines even if it has a line editor.
+#[test]
+fn line_editor_fallback_check() {
+    use crate::promptor::line_editor::LineEditor;
+
+    let input = b"ab\x1b[Dc\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+    promptor.line_editor = Some(LineEditor::new());
+
+    assert_eq!("ab\x1b[Dc", promptor.rget_line("> ").unwrap());
+    assert!(promptor.line_editor.unwrap().history().is_empty());
+    assert_eq!("> ", String::from_utf8(output).unwrap());
+}
+
+/// In this test, I am checking that a prompt with `history()` keeps its valid answers
+/// in a file under `Promptor::history`, across runs, and that other prompts keep nothing.
+#[test]
+fn history_store_check() {
+    use crate::promptor::history::HistoryStore;
+
+    let dir = std::env::temp_dir().join(format!("promptor-history-{}", std::process::id()));
+    let mut store = HistoryStore::new(&dir);
+    store.max_entries = 2;
+
+    for input in &[&b"abc\n8080\n"[..], b"22\n", b"443\n", b"22\n", b"1\n"] {
+        let mut output = Vec::new();
+
+        let mut promptor = Promptor::new(*input, &mut output);
+        promptor.history = Some(store.clone());
+
+        promptor.ask::<u32>("Port").history("port").run().unwrap();
+    }
+
+    let mut output = Vec::new();
+    let mut promptor = Promptor::new(&b"secret\n"[..], &mut output);
+    promptor.history = Some(store.clone());
+    promptor.ask::<String>("Token").run().unwrap();
+
+    let saved = std::fs::read_to_string(store.path("port"));
+    let files = std::fs::read_dir(&dir).unwrap().count();
+    let history = store.load("port");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    // "abc" wasn't a valid answer, and the repeated "22" replaced the earlier one.
+    assert_eq!("22\n1\n", saved.unwrap());
+    assert_eq!(1, files);
+    assert_eq!(&["22".to_owned(), "1".to_owned()], history.unwrap().entries());
+    assert!(store.load("missing").unwrap().entries().is_empty());
+}
+
+/// In this test, I am checking that a history file that can't be read, or a store that can't be written to,
+/// doesn't stop a prompt with `history()` from returning the user's answer.
+#[test]
+fn history_store_failure_check() {
+    use crate::promptor::history::HistoryStore;
+
+    let dir = std::env::temp_dir().join(format!("promptor-history-broken-{}", std::process::id()));
+    let store = HistoryStore::new(&dir);
+
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(store.path("port"), b"\xff\xfe80\n").unwrap();
+
+    let mut output = Vec::new();
+    let mut promptor = Promptor::new(&b"8080\n"[..], &mut output);
+    promptor.history = Some(store.clone());
+
+    let corrupt = promptor.ask::<u32>("Port").history("port").run();
+
+    // A file where the store's directory should be can't be read from or written to.
+    let file = dir.join("not-a-dir");
+    std::fs::write(&file, b"").unwrap();
+
+    let mut output = Vec::new();
+    let mut promptor = Promptor::new(&b"22\n"[..], &mut output);
+    promptor.history = Some(HistoryStore::new(&file));
+
+    let unwritable = promptor.ask::<u32>("Port").history("port").run();
+
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(8080, corrupt.unwrap());
+    assert_eq!(22, unwritable.unwrap());
+}
+
+/// In this test, I am checking that history keys can't name files outside of the store.
+#[test]
+fn history_path_check() {
+    use crate::promptor::history::HistoryStore;
+
+    let store = HistoryStore::new("/state/app");
+
+    assert_eq!(
+        std::path::PathBuf::from("/state/app/deploy_host.history"),
+        store.path("deploy.host")
+    );
+    assert_eq!(
+        std::path::PathBuf::from("/state/app/___etc_passwd.history"),
+        store.path("../etc/passwd")
+    );
+}
+
+/// In this test, I am checking that the line editor can recall lines from a prompt's own history,
+/// without adding to its session history.
+#[test]
+fn line_editor_with_history_check() {
+    use crate::promptor::history::History;
+    use crate::promptor::line_editor::{Helpers, LineEditor};
+
+    let mut history = History::default();
+    history.add("example.com");
+
+    let input = b"\x1b[A\r";
+    let mut reader = &input[..];
+    let mut output = Vec::new();
+
+    let mut editor = LineEditor::new();
+
+    let helpers = Helpers {
+        history: Some(&history),
+        ..Helpers::default()
+    };
+    let res = editor.read_line_with(&mut reader, &mut output, "Host: ", helpers);
+
+    assert_eq!(Some("example.com".to_owned()), res.unwrap());
+    assert!(editor.history().is_empty());
+}
+
+/// In this test, I am checking what the built-in completers suggest.
+#[test]
+fn completer_check() {
+    use crate::completer::{choices, paths, words, Completer, Completion};
+
+    let colours = words(&["red", "green", "grey"]);
+    assert_eq!(
+        Completion {
+            start: 4,
+            candidates: vec!["red".to_owned()]
+        },
+        colours.complete("red r", 5)
+    );
+    assert_eq!(0, colours.complete("blue", 4).candidates.len());
+
+    let fruit = choices(&["Blue apple", "blueberry", "cherry"]);
+    assert_eq!(
+        vec!["Blue apple".to_owned(), "blueberry".to_owned()],
+        fruit.complete("bl", 2).candidates
+    );
+
+    let dir = std::env::temp_dir().join(format!("promptor-paths-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::write(dir.join("Cargo.toml"), "").unwrap();
+    std::fs::write(dir.join("Cargo.lock"), "").unwrap();
+    std::fs::write(dir.join(".hidden"), "").unwrap();
+
+    let prefix = format!("{}/", dir.display());
+    let all = paths().complete(&prefix, prefix.len());
+    let hidden = paths().complete(&format!("{}.", prefix), prefix.len() + 1);
+    let missing = paths().complete("/no/such/dir/x", 14);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(prefix.len(), all.start);
+    assert_eq!(
+        vec!["Cargo.lock".to_owned(), "Cargo.toml".to_owned(), "src/".to_owned()],
+        all.candidates
+    );
+    assert_eq!(vec![".hidden".to_owned()], hidden.candidates);
+    assert_eq!(Completion::default(), missing);
+
+    // A cursor inside a character, or past the end of the line, suggests nothing instead of panicking.
+    assert_eq!(Completion::default(), words(&["é"]).complete("é", 1));
+    assert_eq!(Completion::default(), choices(&["é"]).complete("é", 1));
+    assert_eq!(Completion::default(), paths().complete("é", 1));
+    assert_eq!(Completion::default(), colours.complete("red", 4));
+}
+
+/// In this test, I am checking that Tab fills in what the candidates have in common,
+/// and lists them when there is nothing more to fill in.
+#[test]
+fn line_editor_completion_check() {
+    use crate::completer::words;
+    use crate::promptor::line_editor::{Helpers, LineEditor};
+
+    let colours = words(&["green", "grey", "blue"]);
+    let helpers = Helpers {
+        completer: Some(&colours),
+        ..Helpers::default()
+    };
+
+    // "b", Tab, " g", Tab, Tab, "e", Tab, "x", Tab, Enter.
+    let input = b"b\t g\t\te\tx\t\r";
+    let mut reader = &input[..];
+    let mut output = Vec::new();
+
+    let mut editor = LineEditor::new();
+
+    let res = editor.read_line_with(&mut reader, &mut output, "> ", helpers);
+
+    assert_eq!(Some("blue greenx".to_owned()), res.unwrap());
+    assert!(String::from_utf8(output).unwrap().contains("\r> blue gre\x1b[K\ngreen  grey\n\r> blue gre\x1b[K"));
+}
+
+/// In this test, I am checking that the line editor shows the default as a hint in the given style
+/// on an empty line, that Right accepts it, and that the hint is gone once the user presses Enter.
+#[test]
+fn line_editor_default_hint_check() {
+    use crate::promptor::line_editor::{Helpers, LineEditor};
+    use crate::promptor::theme::Style;
+
+    let helpers = Helpers {
+        default: Some("8080"),
+        hint_style: Style {
+            dim: true,
+            ..Style::default()
+        },
+        color: true,
+        ..Helpers::default()
+    };
+
+    let input = b"\x1b[C1\r\r";
+    let mut reader = &input[..];
+    let mut output = Vec::new();
+
+    let mut editor = LineEditor::new();
+
+    let accepted = editor.read_line_with(&mut reader, &mut output, "Port: ", helpers);
+    let ignored = editor.read_line_with(&mut reader, &mut output, "Port: ", helpers);
+
+    assert_eq!(Some("80801".to_owned()), accepted.unwrap());
+    assert_eq!(Some(String::new()), ignored.unwrap());
+    assert_eq!(
+        "Port: \r\
+         Port: \x1b[2m8080\x1b[0m\x1b[K\x1b[4D\r\
+         Port: 8080\x1b[K\r\
+         Port: 80801\x1b[K\n\
+         Port: \r\
+         Port: \x1b[2m8080\x1b[0m\x1b[K\x1b[4D\r\
+         Port: \x1b[K\n",
+        String::from_utf8(output).unwrap()
+    );
+}
+
+/// In this test, I am checking that the line editor suggests the rest of the newest matching
+/// history entry, or of the only completion, and that Right only accepts it at the end of the line.
+#[test]
+fn line_editor_history_hint_check() {
+    use crate::completer::words;
+    use crate::promptor::history::History;
+    use crate::promptor::line_editor::{Helpers, LineEditor};
+
+    let mut history = History::default();
+    history.add("deploy staging");
+    history.add("deploy production");
+    history.add("status");
+
+    let commands = words(&["rollback"]);
+    let helpers = Helpers {
+        history: Some(&history),
+        completer: Some(&commands),
+        ..Helpers::default()
+    };
+
+    // "d", Right: the newest entry starting with "d" is "deploy production".
+    // "ro", Left, Right, Right: only the last Right is at the end of the line.
+    let input = b"d\x1b[C\rro\x1b[D\x1b[C\x1b[C\r";
+    let mut reader = &input[..];
+    let mut output = Vec::new();
+
+    let mut editor = LineEditor::new();
+
+    let first = editor.read_line_with(&mut reader, &mut output, "$ ", helpers);
+    let second = editor.read_line_with(&mut reader, &mut output, "$ ", helpers);
+
+    assert_eq!(Some("deploy production".to_owned()), first.unwrap());
+    assert_eq!(Some("rollback".to_owned()), second.unwrap());
+}
+
+/// In this test, I am checking that the interactive menu moves its cursor with the arrow keys and j/k,
+/// doesn't move past either end, and shows the chosen item once the user presses Enter.
+#[test]
+fn menu_check() {
+    use crate::promptor::menu::{run_menu, Filter, Look};
+    use crate::promptor::theme::Theme;
+
+    let theme = Theme::default();
+    let look = Look { theme: &theme, color: false };
+
+    let items: Vec<String> = vec!["red".to_owned(), "green".to_owned(), "blue".to_owned()];
+
+    // Down, j, j, k, Enter.
+    let input = b"\x1b[Bjjk\r";
+    let mut reader = &input[..];
+    let mut output = Vec::new();
+
+    let res = run_menu(&mut reader, &mut output, "Colour: ", &items, (80, 24), Filter::Substring, look);
+
+    assert_eq!(Some(1), res.unwrap());
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.starts_with(
+        "\x1b[?25l\rColour: \x1b[K\n\x1b[7m> red\x1b[0m\x1b[K\n  green\x1b[K\n  blue\x1b[K\n\x1b[J"
+    ));
+    assert!(output.ends_with("\x1b[4A\r\x1b[JColour: green\n\x1b[?25h"));
+
+    // A lone Esc, then Enter, which still picks the item under the cursor.
+    let input = b"j\x1b\r";
+    let mut reader = &input[..];
+
+    let res = run_menu(&mut reader, &mut Vec::new(), "Colour: ", &items, (80, 24), Filter::Substring, look);
+
+    assert_eq!(Some(1), res.unwrap());
+}
+
+/// In this test, I am checking that the interactive menu scrolls to keep the cursor on a small screen,
+/// and that typing filters the items.
+#[test]
+fn menu_scroll_filter_check() {
+    use crate::promptor::menu::{run_menu, Filter, Look};
+    use crate::promptor::theme::Theme;
+
+    let theme = Theme::default();
+    let look = Look { theme: &theme, color: false };
+
+    let items: Vec<String> = (1..=10).map(|n| format!("item {}", n)).collect();
+
+    // On a screen with room for two items: End, Up, Enter.
+    let input = b"\x1b[F\x1b[A\r";
+    let mut reader = &input[..];
+    let mut output = Vec::new();
+
+    let res = run_menu(&mut reader, &mut output, "> ", &items, (80, 4), Filter::Substring, look);
+
+    assert_eq!(Some(8), res.unwrap());
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("\r> \x1b[K\n  item 9\x1b[K\n\x1b[7m> item 10\x1b[0m\x1b[K\n\x1b[J"));
+    assert!(!output.contains("item 1\x1b[K"));
+
+    // "1", which matches "item 1" and "item 10", then "x", which matches nothing,
+    // Enter, which doesn't pick anything, Backspace, Down and Enter.
+    let input = b"1x\r\x7f\x1b[B\r";
+    let mut reader = &input[..];
+    let mut output = Vec::new();
+
+    let res = run_menu(&mut reader, &mut output, "> ", &items, (80, 24), Filter::Substring, look);
+
+    assert_eq!(Some(9), res.unwrap());
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("\r> /1\x1b[K\n\x1b[7m> item 1\x1b[0m\x1b[K\n  item 10\x1b[K\n\x1b[J"));
+    assert!(output.contains("\r> /1x\x1b[K\n  No matches.\x1b[K\n\x1b[J"));
+}
+
+/// In this test, I am checking that the interactive menu stops at Ctrl-D and Ctrl-C,
+/// and that a `Promptor` without a terminal falls back to a numbered menu.
+#[test]
+fn menu_fallback_check() {
+    use crate::promptor::menu::{run_menu, Filter, Look};
+    use crate::promptor::theme::Theme;
+
+    let theme = Theme::default();
+    let look = Look { theme: &theme, color: false };
+
+    let items: Vec<String> = vec!["red".to_owned(), "green".to_owned()];
+
+    let input = b"j\x04";
+    let mut reader = &input[..];
+    assert_eq!(
+        None,
+        run_menu(&mut reader, &mut Vec::new(), "", &items, (80, 24), Filter::Substring, look).unwrap()
+    );
+
+    let input = b"\x03";
+    let mut reader = &input[..];
+    assert_eq!(
+        std::io::ErrorKind::Interrupted,
+        run_menu(&mut reader, &mut Vec::new(), "", &items, (80, 24), Filter::Substring, look)
+            .unwrap_err()
+            .kind()
+    );
+
+    let input = b"2\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    assert_eq!(1, promptor.interactive_select("Colour: ", &items).unwrap());
+    assert_eq!("  1) red\n  2) green\nColour: ", String::from_utf8(output).unwrap());
+}
+
+/// In this test, I am checking how `fuzzy_score` matches and ranks candidates.
+#[test]
+fn fuzzy_score_check() {
+    use crate::promptor::fuzzy::fuzzy_score;
+
+    assert_eq!(Some((0, Vec::new())), fuzzy_score("", "anything"));
+    assert_eq!(None, fuzzy_score("xyz", "main"));
+    assert_eq!(vec![0, 3], fuzzy_score("mn", "main").unwrap().1);
+    assert_eq!(vec![0, 4], fuzzy_score("FB", "foo-bar").unwrap().1);
+    assert_eq!(vec![3, 4], fuzzy_score("fB", "fooFBar").unwrap().1);
+    // The shortest match wins over the first one.
+    assert_eq!(vec![3, 4], fuzzy_score("ab", "a-xab").unwrap().1);
+    // Positions count characters, not bytes.
+    assert_eq!(vec![1, 3], fuzzy_score("éo", "Zéro").unwrap().1);
+
+    let score = |candidate| fuzzy_score("prod", candidate).unwrap().0;
+
+    assert!(score("prod") > score("my-production"));
+    assert!(score("my-production") > score("myproduction"));
+    assert!(score("my-production") > score("p-r-o-d"));
+}
+
+/// In this test, I am checking that the fuzzy picker ranks and highlights the items as the user types,
+/// and returns the index of the item in the original list.
+#[test]
+fn fuzzy_menu_check() {
+    use crate::promptor::menu::{run_menu, Filter, Look};
+    use crate::promptor::theme::Theme;
+
+    let theme = Theme::default();
+    let look = Look { theme: &theme, color: false };
+
+    let items: Vec<String> = vec!["staging".to_owned(), "kind-local".to_owned(), "prod-eu".to_owned()];
+
+    // "k", which is typed rather than moving the cursor, then "l", Enter.
+    let input = b"kl\r";
+    let mut reader = &input[..];
+    let mut output = Vec::new();
+
+    let res = run_menu(&mut reader, &mut output, "> ", &items, (80, 24), Filter::Fuzzy, look);
+
+    assert_eq!(Some(1), res.unwrap());
+    assert!(String::from_utf8(output).unwrap().contains(
+        "\r> kl\x1b[K\n\x1b[7m> \x1b[1mk\x1b[22mind-\x1b[1ml\x1b[22mocal\x1b[0m\x1b[K\n\x1b[J"
+    ));
+}
+
+/// In this test, I am checking that without a terminal, the fuzzy picker reads a query,
+/// shows the matches best first, and then reads a number.
+#[test]
+fn fuzzy_select_lines_check() {
+    let items = ["release/1.0", "main", "feature/remote-login", "fix/retry"];
+
+    let input = b"zzz\nrel\n2\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+
+    assert_eq!(2, promptor.fuzzy_select("Branch: ", &items).unwrap());
+    assert_eq!(
+        "Branch: Nothing matches \"zzz\".\nBranch:   1) release/1.0\n  2) feature/remote-login\nNumber: ",
+        String::from_utf8(output).unwrap()
+    );
+}
+
+/// In this test, I am checking that `retry_message` replaces the built-in messages
+/// with the rejected input and the reason filled in, and that a prompt's own `error_message` wins over it.
+#[test]
+fn retry_message_check() {
+    use crate::validator::range;
+
+    let input = b"abc\n{reason}\n500\n7\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+    promptor.retry_message = Some("\"{input}\" won't do ({reason}) {oops}".to_owned());
+
+    let res = promptor.try_prompt_with::<u32, _>("N: ", range(1..=10));
+
+    assert_eq!(7, res.unwrap());
+    assert_eq!(
+        "N: \"abc\" won't do (invalid digit found in string) {oops}\n\
+         N: \"{reason}\" won't do (invalid digit found in string) {oops}\n\
+         N: \"500\" won't do (Input must be between 1 and 10.) {oops}\n\
+         N: ",
+        String::from_utf8(output).unwrap()
+    );
+
+    let input = b"0\n1\nx\n5\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+    promptor.retry_message = Some("Nope: {reason}".to_owned());
+
+    assert_eq!(1, promptor.prompt::<u32, _>("N: ", |&x| x > 0));
+    let res = promptor.ask::<u32>("M").error_message("{input}? Try again.").run();
+
+    assert_eq!(5, res.unwrap());
+    assert_eq!(
+        "N: Nope: Invalid input! Please try again.\nN: M: x? Try again.\nM: ",
+        String::from_utf8(output).unwrap()
+    );
+}
+
+/// In this test, I am checking that `confirm`, `select` and `password_confirmed` use `retry_message` too,
+/// and that `password_confirmed` doesn't show the secret.
+#[test]
+fn retry_message_prompts_check() {
+    let input = b"maybe\ny\n4\n2\nhunter2\nhunter3\nhunter2\nhunter2\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+    promptor.retry_message = Some("Nope: \"{input}\" ({reason})".to_owned());
+
+    assert!(promptor.confirm("Sure?", None).unwrap());
+    assert_eq!(1, promptor.select("Shell: ", &["bash", "fish"]).unwrap());
+    assert_eq!(
+        "hunter2",
+        promptor.password_confirmed("Password: ", "Again: ").unwrap().expose_secret()
+    );
+
+    assert_eq!(
+        "Sure? [y/n] Nope: \"maybe\" (Please answer yes or no.)\n\
+         Sure? [y/n]   1) bash\n  2) fish\n\
+         Shell: Nope: \"4\" (Please enter a number from 1 to 2.)\n\
+         Shell: Password: Again: Nope: \"\" (The entries don't match. Please try again.)\n\
+         Password: Again: ",
+        String::from_utf8(output).unwrap()
+    );
+}
+
+/// In this test, I am checking that retry messages go to `error_writer` when it is set,
+/// while the prompts still go to `writer`.
+#[test]
+fn error_writer_check() {
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let errors = Shared::default();
+
+    let input = b"x\n3\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+    promptor.error_writer = Some(Box::new(errors.clone()));
+
+    assert_eq!(3, promptor.rprompt::<u32, _>("N: ", |_| true));
+    assert_eq!("N: N: ", String::from_utf8(output).unwrap());
+    assert_eq!(
+        "Invalid input: invalid digit found in string. Please try again.\n",
+        String::from_utf8(errors.0.lock().unwrap().clone()).unwrap()
+    );
+}
+
+/// In this test, I am checking that a theme styles the parts of a prompt,
+/// and that without a terminal, prompts only get the theme's symbols, without any escape sequences.
+#[test]
+fn theme_check() {
+    use crate::promptor::theme::Theme;
+
+    let theme = Theme::colorful();
+
+    assert_eq!("\x1b[1;36m? \x1b[0m\x1b[1mPort\x1b[0m", theme.prompt("Port", true));
+    assert_eq!("? Port", theme.prompt("Port", false));
+    assert_eq!("\x1b[2m[8080]\x1b[0m", theme.default_value("[8080]", true));
+    assert_eq!("\x1b[31m✘ Too big.\x1b[0m", theme.error("Too big.", true));
+    assert_eq!(
+        Some("✔ Port · 8080".to_owned()),
+        theme.success_line("Port: ", "8080", false)
+    );
+    assert_eq!(None, Theme::default().success_line("Port: ", "8080", false));
+
+    let input = b"99999\n8\ny\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+    promptor.capabilities = Default::default();
+    promptor.theme = theme;
+
+    let port = promptor.ask::<u8>("Port").default(80).run();
+    let sure = promptor.confirm("Sure?", None);
+
+    assert_eq!(8, port.unwrap());
+    assert!(sure.unwrap());
+    assert_eq!(
+        "? Port [80]: ✘ Invalid input: number too large to fit in target type. Please try again.\n\
+         ? Port [80]: ? Sure? [y/n] ",
+        String::from_utf8(output).unwrap()
+    );
+}
+
+/// In this test, I am checking that the interactive menu uses the theme's markers,
+/// and replaces itself with the theme's success line.
+#[test]
+fn menu_theme_check() {
+    use crate::promptor::menu::{run_menu, Filter, Look};
+    use crate::promptor::theme::Theme;
+
+    let theme = Theme::colorful();
+    let look = Look { theme: &theme, color: false };
+
+    let items: Vec<String> = vec!["red".to_owned(), "green".to_owned()];
+
+    let input = b"j\r";
+    let mut reader = &input[..];
+    let mut output = Vec::new();
+
+    let res = run_menu(&mut reader, &mut output, "Colour: ", &items, (80, 24), Filter::Substring, look);
+
+    assert_eq!(Some(1), res.unwrap());
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("\r? Colour: \x1b[K\n\x1b[7m❯ red\x1b[0m\x1b[K\n  green\x1b[K\n\x1b[J"));
+    assert!(output.ends_with("\x1b[3A\r\x1b[J✔ Colour · green\n\x1b[?25h"));
+}
+
+/// In this test, I am checking how `Capabilities` reads `NO_COLOR`, `CLICOLOR_FORCE` and `TERM`.
+#[test]
+fn capabilities_check() {
+    use crate::promptor::capabilities::Capabilities;
+
+    let detect = |input, output, vars: &[(&str, &str)]| {
+        let vars: Vec<(String, String)> =
+            vars.iter().map(|&(name, value)| (name.to_owned(), value.to_owned())).collect();
+
+        Capabilities::from_vars(input, output, |name| {
+            vars.iter().find(|(var, _)| var == name).map(|(_, value)| value.into())
+        })
+    };
+
+    let tty = detect(true, true, &[("TERM", "xterm-256color")]);
+    assert!(tty.interactive() && tty.color());
+
+    let piped = detect(true, false, &[]);
+    assert!(!piped.interactive() && !piped.color());
+
+    let no_color = detect(true, true, &[("NO_COLOR", "1")]);
+    assert!(no_color.interactive() && !no_color.color());
+    assert!(detect(true, true, &[("NO_COLOR", "")]).color());
+
+    assert!(detect(false, false, &[("CLICOLOR_FORCE", "1")]).color());
+    assert!(!detect(false, false, &[("CLICOLOR_FORCE", "0")]).color());
+    assert!(!detect(true, true, &[("CLICOLOR_FORCE", "1"), ("NO_COLOR", "1")]).color());
+
+    let dumb = detect(true, true, &[("TERM", "dumb")]);
+    assert!(!dumb.interactive() && !dumb.color());
+    assert!(detect(true, true, &[("TERM", "dumb"), ("CLICOLOR_FORCE", "1")]).color());
+}
+
+/// In this test, I am checking that overriding `Capabilities` switches a `Promptor` without a terminal
+/// between the plain and the interactive, styled behaviour.
+#[test]
+fn capabilities_override_check() {
+    use crate::promptor::capabilities::Capabilities;
+    use crate::promptor::line_editor::LineEditor;
+    use crate::promptor::theme::{Color, Style, Theme};
+
+    let interactive = Capabilities {
+        input_is_terminal: true,
+        output_is_terminal: true,
+        ..Capabilities::default()
+    };
+
+    // The line editor reads keys, Left and "1" here, and the answer replaces the prompt.
+    let input = b"8080\x1b[D1\r";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+    promptor.capabilities = interactive;
+    promptor.line_editor = Some(LineEditor::new());
+    promptor.theme = Theme::colorful();
+
+    assert_eq!(80810, promptor.ask::<u32>("Port").run().unwrap());
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.starts_with("\x1b[1;36m? \x1b[0m\x1b[1mPort\x1b[0m: \r"));
+    assert!(output.contains("\x1b[1mPort\x1b[0m: 80810\x1b[K\x1b[1D\n"));
+    assert!(output.ends_with(
+        "\x1b[1A\r\x1b[K\x1b[1;32m✔\x1b[0m \x1b[1mPort\x1b[0m · \x1b[36m80810\x1b[0m\n"
+    ));
+
+    // TERM=dumb falls back to plain lines, even with the line editor, and drops the colours.
+    let input = b"8080\n";
+    let mut output = Vec::new();
+
+    let mut promptor = Promptor::new(&input[..], &mut output);
+    promptor.capabilities = Capabilities { dumb: true, ..interactive };
+    promptor.line_editor = Some(LineEditor::new());
+    promptor.theme = Theme::colorful();
+
+    assert_eq!(8080, promptor.ask::<u32>("Port").run().unwrap());
+    assert_eq!("? Port: ", String::from_utf8(output).unwrap());
+
+    // The line editor's hint for the default is styled like the `[8080]` next to the prompt,
+    // and not at all with NO_COLOR.
+    let mut theme = Theme::colorful();
+    theme.default = Style {
+        color: Some(Color::Yellow),
+        ..Style::default()
+    };
+
+    for &no_color in &[false, true] {
+        let input = b"\r";
+        let mut output = Vec::new();
+
+        let mut promptor = Promptor::new(&input[..], &mut output);
+        promptor.capabilities = Capabilities { no_color, ..interactive };
+        promptor.line_editor = Some(LineEditor::new());
+        promptor.theme = theme.clone();
+
+        assert_eq!(8080, promptor.ask::<u32>("Port").default(8080).run().unwrap());
+
+        let output = String::from_utf8(output).unwrap();
+        if no_color {
+            assert!(output.contains("? Port [8080]: 8080\x1b[K\x1b[4D"));
+            assert!(!output.contains("\x1b[33m"));
+        } else {
+            assert!(output.contains("\x1b[33m[8080]\x1b[0m: \x1b[33m8080\x1b[0m\x1b[K\x1b[4D"));
+        }
+    }
+}
+
+/// In this test, I am checking that a `ScriptedPromptor` answers the questions in its script,
+/// including after a list or a retry message, and records the transcript.
+#[test]
+fn scripted_promptor_check() {
+    use crate::testing::ScriptedPromptor;
+
+    let mut scripted = ScriptedPromptor::new(vec![
+        ("Shell:", "4"),
+        ("Shell:", "fish"),
+        ("Overwrite ~/.config? [y/N]", ""),
+    ]);
+
+    assert_eq!(1, scripted.select("Shell: ", &["bash", "fish", "zsh"]).unwrap());
+    assert!(!scripted.confirm("Overwrite ~/.config?", Some(false)).unwrap());
+
+    assert_eq!(
+        "  1) bash\n  2) fish\n  3) zsh\nShell: 4\nPlease enter a number from 1 to 3.\nShell: fish\n\
+         Overwrite ~/.config? [y/N] \n",
+        scripted.finish()
+    );
+}
+
+/// In this test, I am checking that a `ScriptedPromptor` shows the difference
+/// when the code asks something the script doesn't expect.
+#[test]
+#[should_panic(expected = "question 2 didn't match the script:\n- expected: Port [8080]:\n+ asked:    Port [80]:")]
+fn scripted_promptor_mismatch_check() {
+    use crate::testing::ScriptedPromptor;
+
+    let mut scripted = ScriptedPromptor::new(vec![("Host:", "localhost"), ("Port [8080]:", "")]);
+
+    scripted.rget_line("Host: ").unwrap();
+    let _ = scripted.ask::<u32>("Port").default(80).run();
+}
+
+/// In this test, I am checking that a `ScriptedPromptor` fails when the code asks more questions than the script has.
+#[test]
+#[should_panic(expected = "question 2 wasn't in the script, which only has 1:\n+ asked:    Port:")]
+fn scripted_promptor_too_many_check() {
+    use crate::testing::ScriptedPromptor;
+
+    let mut scripted = ScriptedPromptor::new(vec![("Host:", "localhost")]);
+
+    scripted.rget_line("Host: ").unwrap();
+    let _ = scripted.rget_line("Port: ");
+}
+
+/// In this test, I am checking that a `ScriptedPromptor` fails when it is dropped
+/// before the code has asked every question in the script.
+#[test]
+#[should_panic(expected = "only 1 of the 2 questions in the script were asked; these never were:\n- Port:")]
+fn scripted_promptor_too_few_check() {
+    use crate::testing::ScriptedPromptor;
+
+    let mut scripted = ScriptedPromptor::new(vec![("Host:", "localhost"), ("Port:", "80")]);
+
+    scripted.rget_line("Host: ").unwrap();
+}